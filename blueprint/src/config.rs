@@ -11,6 +11,11 @@ pub struct Config {
     #[serde(default)]
     pub routing: Vec<RoutingInfo>,
 
+    /// bind access path to a specific file (e.g. `"/about" = "company/about.md"`)
+    #[serde(rename = "file-map")]
+    #[serde(default)]
+    pub file_map: HashMap<String, String>,
+
     #[serde(rename = "data-source")]
     pub data_source: DeployDataSourceConfig,
 
@@ -19,6 +24,231 @@ pub struct Config {
     pub footer: FooterConfig,
 
     pub giscus: Option<GiscusConfig>,
+
+    #[serde(default)]
+    pub i18n: Option<I18nConfig>,
+
+    #[serde(default)]
+    pub analytics: Option<AnalyticsConfig>,
+
+    #[serde(rename = "error-page")]
+    #[serde(default)]
+    pub error_page: ErrorPageConfig,
+}
+
+pub const SUPPORTED_SERVICES: [&str; 3] = ["github", "gitee", "gitlab"];
+pub const SUPPORTED_MARKDOWN_FLAVORS: [&str; 2] = ["gfm", "commonmark"];
+pub const SUPPORTED_TRANSITIONS: [&str; 3] = ["none", "fade", "slide"];
+pub const SUPPORTED_NAVBAR_MODES: [&str; 3] = ["static", "sticky", "transparent"];
+pub const SUPPORTED_NAVBAR_ALIGNS: [&str; 2] = ["left", "right"];
+pub const SUPPORTED_ANALYTICS_PROVIDERS: [&str; 2] = ["plausible", "umami"];
+/// highlight.js styles in `public/assets/highlight/styles`.
+pub const SUPPORTED_CODE_THEMES: [&str; 13] = [
+    "dark",
+    "default",
+    "dracula",
+    "github",
+    "github-dark",
+    "idea",
+    "monokai",
+    "obsidian",
+    "rainbow",
+    "stackoverflow-dark",
+    "stackoverflow-light",
+    "vs",
+    "xcode",
+];
+pub const SUPPORTED_SOURCE_MODES: [&str; 4] = [
+    "independent-repository",
+    "embedded-repository",
+    "custom-url",
+    "local",
+];
+
+/// all problems found by `Config::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError(pub Vec<String>);
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid configuration:")?;
+        for problem in &self.0 {
+            write!(f, "\n- {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// check required fields & allowed values, all problems will be reported together.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = vec![];
+
+        if self.site.name.trim().is_empty() {
+            problems.push("site.name must not be empty".to_string());
+        }
+        if let Some(flavor) = &self.site.markdown_flavor {
+            if !SUPPORTED_MARKDOWN_FLAVORS.contains(&flavor.to_lowercase().as_str()) {
+                problems.push(format!(
+                    "site.markdown-flavor `{}` is not supported, expected one of: {}",
+                    flavor,
+                    SUPPORTED_MARKDOWN_FLAVORS.join(", ")
+                ));
+            }
+        }
+        if let Some(transition) = &self.site.transition {
+            if !SUPPORTED_TRANSITIONS.contains(&transition.as_str()) {
+                problems.push(format!(
+                    "site.transition `{}` is not supported, expected one of: {}",
+                    transition,
+                    SUPPORTED_TRANSITIONS.join(", ")
+                ));
+            }
+        }
+
+        for theme in [&self.site.code_theme.light, &self.site.code_theme.dark] {
+            if !theme.contains('/') && !SUPPORTED_CODE_THEMES.contains(&theme.as_str()) {
+                problems.push(format!(
+                    "site.code-theme `{}` is not supported, expected a stylesheet url or one of: {}",
+                    theme,
+                    SUPPORTED_CODE_THEMES.join(", ")
+                ));
+            }
+        }
+
+        // router only skips the first segment of location path
+        if self.site.base_path().matches('/').count() > 1 {
+            problems.push(format!(
+                "site.base-path `{}` must be a single path segment, e.g. `/my-site`",
+                self.site.base_path()
+            ));
+        }
+
+        if let Some(mode) = &self.navigation.mode {
+            if !SUPPORTED_NAVBAR_MODES.contains(&mode.as_str()) {
+                problems.push(format!(
+                    "navigation.mode `{}` is not supported, expected one of: {}",
+                    mode,
+                    SUPPORTED_NAVBAR_MODES.join(", ")
+                ));
+            }
+        }
+        for item in &self.navigation.content {
+            if let Some(align) = &item.align {
+                if !SUPPORTED_NAVBAR_ALIGNS.contains(&align.as_str()) {
+                    problems.push(format!(
+                        "navigation.content align `{}` is not supported, expected one of: {}",
+                        align,
+                        SUPPORTED_NAVBAR_ALIGNS.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if let Some(i18n) = &self.i18n {
+            if !i18n.languages.contains(&i18n.default) {
+                problems.push(format!(
+                    "i18n.default `{}` must be one of i18n.languages: {}",
+                    i18n.default,
+                    i18n.languages.join(", ")
+                ));
+            }
+        }
+
+        if let Some(provider) = self.analytics.as_ref().and_then(|v| v.provider.as_ref()) {
+            if !SUPPORTED_ANALYTICS_PROVIDERS.contains(&provider.as_str()) {
+                problems.push(format!(
+                    "analytics.provider `{}` is not supported, expected one of: {}",
+                    provider,
+                    SUPPORTED_ANALYTICS_PROVIDERS.join(", ")
+                ));
+            }
+        }
+
+        let service = self.repository.service.to_lowercase();
+        if !SUPPORTED_SERVICES.contains(&service.as_str()) {
+            problems.push(format!(
+                "repository.service `{}` is not supported, expected one of: {}",
+                self.repository.service,
+                SUPPORTED_SERVICES.join(", ")
+            ));
+        }
+        if !self.repository.name.contains('/') {
+            problems.push(format!(
+                "repository.name `{}` must be `{{username}}/{{repo-name}}`",
+                self.repository.name
+            ));
+        }
+
+        validate_source(
+            "data-source",
+            &self.data_source.mode,
+            &self.data_source.data,
+            &mut problems,
+        );
+        if let Some(local) = &self.data_source.local {
+            validate_source("data-source.local", &local.mode, &local.data, &mut problems);
+        }
+        for (index, mirror) in self.data_source.mirrors.iter().enumerate() {
+            let name = format!("data-source.mirrors[{}]", index);
+            validate_source(&name, &mirror.mode, &mirror.data, &mut problems);
+        }
+        if self.data_source.concurrency == 0 {
+            problems.push("data-source.concurrency must be greater than 0".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError(problems))
+        }
+    }
+}
+
+fn validate_source(name: &str, mode: &str, data: &toml::Value, problems: &mut Vec<String>) {
+    let required = |keys: &[&str], problems: &mut Vec<String>| match data.as_table() {
+        Some(table) => {
+            for key in keys {
+                if table.get(*key).and_then(|v| v.as_str()).is_none() {
+                    problems.push(format!("{}.data.{} is missing or not a string", name, key));
+                }
+            }
+        }
+        None => problems.push(format!("{}.data must be a table in `{}` mode", name, mode)),
+    };
+    match mode.to_lowercase().as_str() {
+        "independent-repository" => {
+            required(&["service", "name"], problems);
+            if data.get("branch").map(|v| !v.is_str()).unwrap_or(false) {
+                problems.push(format!("{}.data.branch must be a string", name));
+            }
+            let service = data.get("service").and_then(|v| v.as_str());
+            if let Some(service) = service {
+                if !SUPPORTED_SERVICES.contains(&service.to_lowercase().as_str()) {
+                    problems.push(format!(
+                        "{}.data.service `{}` is not supported, expected one of: {}",
+                        name,
+                        service,
+                        SUPPORTED_SERVICES.join(", ")
+                    ));
+                }
+            }
+        }
+        "custom-url" => required(&["url", "index-file"], problems),
+        "embedded-repository" | "local" => {
+            if !data.is_str() {
+                problems.push(format!("{}.data must be a string in `{}` mode", name, mode));
+            }
+        }
+        _ => problems.push(format!(
+            "{}.mode `{}` is not supported, expected one of: {}",
+            name,
+            mode,
+            SUPPORTED_SOURCE_MODES.join(", ")
+        )),
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
@@ -28,19 +258,295 @@ pub struct SiteConfig {
     pub title_suffix: String,
     #[serde(rename = "dark-mode")]
     pub dark_mode: bool,
+    /// default page description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// default preview image for social sharing
+    #[serde(default)]
+    pub image: Option<String>,
+    /// logo image url displayed in navbar
+    #[serde(default)]
+    pub logo: Option<String>,
+    /// only display the logo in navbar (site name is used as image alt)
+    #[serde(rename = "hide-name")]
+    #[serde(default)]
+    pub hide_name: bool,
+    /// favicon url, replace the default `favicon.ico`
+    #[serde(default)]
+    pub favicon: Option<String>,
+    /// markdown extension set: `gfm` (default) or `commonmark`
+    #[serde(rename = "markdown-flavor")]
+    #[serde(default)]
+    pub markdown_flavor: Option<String>,
+    /// markdown file (in data source) displayed as not found page, e.g. `pages/404.md`
+    #[serde(rename = "not-found")]
+    #[serde(default)]
+    pub not_found: Option<String>,
+    /// `strftime` pattern of displayed post dates, e.g. `%B %d, %Y`
+    #[serde(rename = "date-format")]
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// page enter transition: `none` (default), `fade` or `slide`
+    #[serde(default)]
+    pub transition: Option<String>,
+    #[serde(rename = "external-link")]
+    #[serde(default)]
+    pub external_link: ExternalLinkConfig,
+    /// prefetch internal page content when link is hovered
+    #[serde(default = "default_true")]
+    pub prefetch: bool,
+    /// hide navbar & footer and use light colors when printing
+    #[serde(rename = "print-style")]
+    #[serde(default = "default_true")]
+    pub print_style: bool,
+    /// render `$...$` & `$$...$$` math in markdown, KaTeX is loaded only when enabled
+    #[serde(default)]
+    pub math: bool,
+    /// render ```` ```mermaid ```` code blocks as diagrams, mermaid is loaded only when enabled
+    #[serde(default)]
+    pub mermaid: bool,
+    /// mermaid script url (e.g. self-hosted copy), replace the default CDN script
+    #[serde(rename = "mermaid-script")]
+    #[serde(default)]
+    pub mermaid_script: Option<String>,
+    /// subresource integrity (`sha384-...`) of `mermaid-script`
+    #[serde(rename = "mermaid-integrity")]
+    #[serde(default)]
+    pub mermaid_integrity: Option<String>,
+    /// register `/sw.js` service worker, visited pages can be read offline
+    #[serde(default)]
+    pub offline: bool,
+    /// custom stylesheet url, loaded after the default styles
+    #[serde(rename = "custom-css")]
+    #[serde(default)]
+    pub custom_css: Option<String>,
+    /// inline css rules, applied after `custom-css`
+    #[serde(rename = "custom-style")]
+    #[serde(default)]
+    pub custom_style: Option<String>,
+    /// sub path the site is deployed under, e.g. `/my-site` for `https://example.com/my-site/`
+    #[serde(rename = "base-path")]
+    #[serde(default)]
+    pub base_path: Option<String>,
+    /// code highlight theme of light & dark mode
+    #[serde(rename = "code-theme")]
+    #[serde(default)]
+    pub code_theme: CodeThemeConfig,
+}
+
+/// highlight.js theme name (see `SUPPORTED_CODE_THEMES`) or a stylesheet url (contains `/`).
+#[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
+pub struct CodeThemeConfig {
+    #[serde(default = "default_light_code_theme")]
+    pub light: String,
+    #[serde(default = "default_dark_code_theme")]
+    pub dark: String,
+}
+
+impl Default for CodeThemeConfig {
+    fn default() -> Self {
+        Self {
+            light: default_light_code_theme(),
+            dark: default_dark_code_theme(),
+        }
+    }
+}
+
+impl CodeThemeConfig {
+    /// stylesheet path of the theme used by the color mode.
+    pub fn stylesheet(&self, dark: bool) -> String {
+        let theme = if dark { &self.dark } else { &self.light };
+        if theme.contains('/') {
+            theme.clone()
+        } else if theme == "dracula" {
+            String::from("/assets/highlight/styles/dracula.css")
+        } else {
+            format!("/assets/highlight/styles/{theme}.min.css")
+        }
+    }
+}
+
+fn default_light_code_theme() -> String {
+    String::from("github")
+}
+
+fn default_dark_code_theme() -> String {
+    String::from("dracula")
+}
+
+impl SiteConfig {
+    /// normalized `base-path` (`/my-site`), empty when the site is deployed at root.
+    pub fn base_path(&self) -> String {
+        let path = self.base_path.as_deref().unwrap_or_default().trim_matches('/');
+        if path.is_empty() {
+            String::new()
+        } else {
+            format!("/{path}")
+        }
+    }
+
+    /// prepend base path to an absolute path (`/docs` -> `/my-site/docs`),
+    /// relative path & external url are returned as is.
+    pub fn with_base(&self, path: &str) -> String {
+        if path.starts_with('/') && !path.starts_with("//") {
+            format!("{}{}", self.base_path(), path)
+        } else {
+            path.to_string()
+        }
+    }
+
+    /// remove base path from the location path (`/my-site/docs` -> `/docs`).
+    pub fn strip_base(&self, path: &str) -> String {
+        let base = self.base_path();
+        match path.strip_prefix(&base) {
+            Some(rest) if !base.is_empty() && (rest.is_empty() || rest.starts_with('/')) => {
+                format!("/{}", rest.trim_start_matches('/'))
+            }
+            _ => path.to_string(),
+        }
+    }
+}
+
+/// available content languages, translated files are stored in `{lang}` sub directory,
+/// e.g. `pages/zh/about.md` for `pages/about.md`.
+#[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
+pub struct I18nConfig {
+    pub languages: Vec<String>,
+    /// language of the original files
+    pub default: String,
+    /// display name of languages, e.g. `{ en = "English", zh = "中文" }`
+    #[serde(default)]
+    pub names: HashMap<String, String>,
+}
+
+impl I18nConfig {
+    /// file path of the translation, `None` for default or unknown language.
+    pub fn localized_path(&self, path: &str, lang: &str) -> Option<String> {
+        if lang == self.default || !self.languages.iter().any(|v| v == lang) {
+            return None;
+        }
+        Some(match path.rsplit_once('/') {
+            Some((dir, name)) => format!("{dir}/{lang}/{name}"),
+            None => format!("{lang}/{path}"),
+        })
+    }
+
+    pub fn display_name<'a>(&'a self, lang: &'a str) -> &'a str {
+        self.names.get(lang).map(|v| v.as_str()).unwrap_or(lang)
+    }
+}
+
+/// copy & style of the error page (content load or parse failure).
+#[derive(Debug, Serialize, PartialEq, Deserialize, Clone, Default)]
+pub struct ErrorPageConfig {
+    /// title template, `{title}` is replaced by the error title, e.g. `Oops! {title}`
+    #[serde(default)]
+    pub title: Option<String>,
+    /// message displayed under the error, e.g. `Please try again later.`
+    #[serde(default)]
+    pub body: Option<String>,
+    /// url of the "report" link, e.g. the issue page of the content repository
+    #[serde(rename = "report-link")]
+    #[serde(default)]
+    pub report_link: Option<String>,
+    /// text of the "report" link, default is `Report this problem`
+    #[serde(rename = "report-text")]
+    #[serde(default)]
+    pub report_text: Option<String>,
+    /// extra class of the error page wrapper
+    #[serde(default)]
+    pub class: Option<String>,
+}
+
+impl ErrorPageConfig {
+    /// displayed title of the error, see `title`.
+    pub fn title(&self, title: &str) -> String {
+        match &self.title {
+            Some(template) => template.replace("{title}", title),
+            None => title.to_string(),
+        }
+    }
+}
+
+/// third-party analytics script, it's only loaded when configured.
+#[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
+pub struct AnalyticsConfig {
+    /// script url, e.g. `https://plausible.io/js/script.manual.js`
+    pub script: String,
+    /// extra attributes of the script tag, e.g. `{ data-domain = "example.com" }`
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    /// `plausible` or `umami`, pageview will be recorded on every route change
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// record clicks of links which leave the site, `provider` is required
+    #[serde(default)]
+    pub outbound: bool,
+}
+
+/// behavior of links which leave the site.
+#[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
+pub struct ExternalLinkConfig {
+    /// add `rel="nofollow"` to external links
+    #[serde(default)]
+    pub nofollow: bool,
+    /// display an icon after external text links
+    #[serde(default = "default_true")]
+    pub icon: bool,
+}
+
+impl Default for ExternalLinkConfig {
+    fn default() -> Self {
+        Self {
+            nofollow: false,
+            icon: true,
+        }
+    }
+}
+
+impl ExternalLinkConfig {
+    /// absolute `http(s)` url leaves the site.
+    pub fn is_external(url: &str) -> bool {
+        let url = url.trim().to_ascii_lowercase();
+        url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+    }
+
+    /// `rel` attribute for the link, `noopener noreferrer` is always used for `_blank` target.
+    pub fn rel(&self, url: &str, target: &str) -> String {
+        let mut rel = vec![];
+        if target == "_blank" {
+            rel.extend(["noopener", "noreferrer"]);
+        }
+        if self.nofollow && Self::is_external(url) {
+            rel.push("nofollow");
+        }
+        rel.join(" ")
+    }
+
+    /// whether the external icon should be displayed after the link.
+    pub fn show_icon(&self, url: &str) -> bool {
+        self.icon && Self::is_external(url)
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
 pub struct DeployRepositoryConfig {
     pub service: String,
     pub name: String,
-    #[serde(default = "default_branch")]
-    pub branch: String,
+    /// `main` then `master` will be probed when it is not configured
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// custom service host, use for self-hosted service (e.g. `gitlab.example.com`)
+    #[serde(default)]
+    pub host: Option<String>,
+    /// custom api endpoint, e.g. `https://git.example.com/api/v3` for github enterprise
+    #[serde(rename = "api-base")]
+    #[serde(default)]
+    pub api_base: Option<String>,
 }
 
-fn default_branch() -> String {
-    String::from("main")
-}
+/// default branches probed in order when repository branch is not configured.
+pub const DEFAULT_BRANCHES: [&str; 2] = ["main", "master"];
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
 pub struct DeployDataSourceConfig {
@@ -48,6 +554,66 @@ pub struct DeployDataSourceConfig {
     pub data: toml::Value,
     #[serde(default)]
     pub local: Option<DeployLocalDataSourceConfig>,
+    /// mirror data sources, used in order when the primary source is unavailable
+    #[serde(default)]
+    pub mirrors: Vec<DeployLocalDataSourceConfig>,
+    /// access token for private repository, it's readable by every visitor of the site
+    #[serde(default)]
+    pub token: Option<String>,
+    /// retry times for failed request (network error & 5xx)
+    #[serde(rename = "retry-count")]
+    #[serde(default = "default_retry_count")]
+    pub retry_count: u32,
+    /// base delay (ms) of retry backoff
+    #[serde(rename = "retry-delay")]
+    #[serde(default = "default_retry_delay")]
+    pub retry_delay: u32,
+    /// request timeout (seconds), `0` disables timeout
+    #[serde(default = "default_timeout")]
+    pub timeout: u32,
+    /// maximum number of content files fetched at the same time
+    #[serde(default = "default_concurrency")]
+    pub concurrency: u32,
+    /// folder (in data source) where all content files are stored, e.g. `content`,
+    /// paths in routing & file map are relative to it. default is the data source root
+    #[serde(rename = "content-dir")]
+    #[serde(default)]
+    pub content_dir: Option<String>,
+}
+
+impl DeployDataSourceConfig {
+    /// prepend `content-dir` to a content file path.
+    pub fn content_path(&self, sub_path: &str) -> String {
+        let dir = self
+            .content_dir
+            .as_deref()
+            .unwrap_or_default()
+            .trim_matches('/');
+        if dir.is_empty() {
+            return sub_path.to_string();
+        }
+        // `./` is the root of content dir
+        match sub_path.trim_start_matches("./") {
+            "" => dir.to_string(),
+            sub_path => format!("{}/{}", dir, sub_path),
+        }
+    }
+}
+
+fn default_retry_count() -> u32 {
+    3
+}
+
+fn default_timeout() -> u32 {
+    15
+}
+
+fn default_retry_delay() -> u32 {
+    500
+}
+
+fn default_concurrency() -> u32 {
+    6
 }
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
@@ -58,17 +624,76 @@ pub struct DeployLocalDataSourceConfig {
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
 pub struct NavigationConfig {
-    pub content: Vec<NavigationInfo>,
+    pub content: Vec<NavigationItem>,
+    /// navbar position: `static` (default), `sticky` or `transparent` (over page top, opaque after scrolling)
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// space (px) kept above the anchor target when jumping to `#section`,
+    /// default is the navbar height for `sticky` & `transparent` mode, otherwise `0`
+    #[serde(rename = "anchor-offset")]
+    #[serde(default)]
+    pub anchor_offset: Option<u32>,
+    /// target of the site brand (logo & name) link, default is `/`
+    #[serde(default)]
+    pub home: Option<String>,
+}
+
+/// navbar height (px, `h-16`) plus a little spacing.
+const NAVBAR_ANCHOR_OFFSET: u32 = 80;
+
+impl NavigationConfig {
+    /// scroll margin of anchor targets, see `anchor_offset`.
+    pub fn anchor_offset(&self) -> u32 {
+        self.anchor_offset.unwrap_or(match self.mode.as_deref() {
+            Some("sticky") | Some("transparent") => NAVBAR_ANCHOR_OFFSET,
+            _ => 0,
+        })
+    }
+
+    /// target of the site brand link, see `home`.
+    pub fn home(&self) -> String {
+        self.home.clone().unwrap_or_else(|| String::from("/"))
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq, Deserialize, Clone)]
 pub struct FooterConfig {
+    #[serde(default = "default_true")]
+    pub enable: bool,
+    #[serde(default)]
     pub content: Vec<Vec<NavigationInfo>>,
+    #[serde(default)]
+    pub copyright: Option<String>,
+}
+
+/// navbar item, `align` decides which side of the navbar it's placed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NavigationItem {
+    #[serde(flatten)]
+    pub info: NavigationInfo,
+    /// `right` (default) or `left` (next to the site brand)
+    #[serde(default)]
+    pub align: Option<String>,
+}
+
+impl NavigationItem {
+    pub fn is_left(&self) -> bool {
+        self.align.as_deref() == Some("left")
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum NavigationInfo {
+    /// dropdown submenu, `page` makes the text itself a link
+    Collection {
+        text: String,
+        #[serde(alias = "children")]
+        list: Vec<NavigationInfo>,
+        #[serde(default)]
+        page: Option<String>,
+    },
+
     TextToPage {
         text: String,
         page: String,
@@ -91,11 +716,6 @@ pub enum NavigationInfo {
         feature: String,
     },
 
-    Collection {
-        text: String,
-        list: Vec<NavigationInfo>,
-    },
-
     PlainText {
         text: String,
     },
@@ -189,3 +809,69 @@ fn default_true() -> bool {
 fn default_false() -> bool {
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(site_name: &str, service: &str, mode: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+                [site]
+                name = "{site_name}"
+                title-suffix = " | Test"
+                dark-mode = false
+
+                [repository]
+                service = "{service}"
+                name = "user/site"
+
+                [data-source]
+                mode = "{mode}"
+                data = "data"
+
+                [navigation]
+                content = []
+
+                [footer]
+                content = []
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn valid_config() {
+        assert_eq!(
+            config("Test", "GitHub", "embedded-repository").validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn all_problems_are_reported() {
+        let ConfigError(problems) = config(" ", "svn", "ftp").validate().unwrap_err();
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("site.name"), "{problems:?}");
+        assert!(
+            problems[1].starts_with("repository.service `svn`"),
+            "{problems:?}"
+        );
+        assert!(
+            problems[2].starts_with("data-source.mode `ftp`"),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn unknown_source_service() {
+        let mut config = config("Test", "GitHub", "independent-repository");
+        config.data_source.data = toml::from_str("service = \"svn\"\nname = \"a/b\"").unwrap();
+        let ConfigError(problems) = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(
+            problems[0].starts_with("data-source.data.service `svn`"),
+            "{problems:?}"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! front matter parser shared by templates, the app & `build.rs`,
+//! it only depends on `std` & `toml` so the build script can include it.
+
+use std::collections::HashMap;
+
+use toml::Value;
+
+/// parse front matter from markdown content, return (meta data, body content).
+///
+/// support YAML style (`---`) and TOML style (`+++`) front matter,
+/// YAML style only support `key: value` pairs, block lists (`- item` lines) & wrapped values,
+/// malformed lines are skipped.
+pub fn parse_front_matter(content: &str) -> Option<(HashMap<String, Value>, String)> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.lines();
+    let fence = lines.next()?.trim_end();
+    if fence != "---" && fence != "+++" {
+        return None;
+    }
+
+    let mut meta = vec![];
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == fence {
+            closed = true;
+            break;
+        }
+        meta.push(line);
+    }
+    if !closed {
+        return None;
+    }
+    let body = lines.collect::<Vec<&str>>().join("\n");
+
+    let meta = if fence == "+++" {
+        toml::from_str::<toml::Table>(&meta.join("\n"))
+            .ok()?
+            .into_iter()
+            .collect()
+    } else {
+        yaml_meta(&meta)
+    };
+
+    Some((meta, body))
+}
+
+fn yaml_meta(lines: &[&str]) -> HashMap<String, Value> {
+    let mut result = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        // indented lines belong to the previous key: list items or wrapped value
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        if let (true, Some(key)) = (indented || trimmed.starts_with("- "), &last_key) {
+            let value = result.get_mut(key);
+            match (trimmed.strip_prefix("- "), value) {
+                (Some(item), Some(Value::Array(list))) => {
+                    list.push(Value::String(unquote(item.trim()).to_string()))
+                }
+                (Some(item), Some(value)) if value.as_str() == Some("") => {
+                    *value = Value::Array(vec![Value::String(unquote(item.trim()).to_string())])
+                }
+                (None, Some(Value::String(value))) if indented => {
+                    if !value.is_empty() {
+                        value.push(' ');
+                    }
+                    value.push_str(unquote(trimmed));
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        result.insert(key.clone(), yaml_value(value.trim()));
+        last_key = Some(key);
+    }
+    result
+}
+
+fn yaml_value(value: &str) -> Value {
+    if value.starts_with('[') && value.ends_with(']') {
+        let list = value[1..value.len() - 1]
+            .split(',')
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(|v| Value::String(unquote(v).to_string()))
+            .collect();
+        return Value::Array(list);
+    }
+    match value {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        _ => Value::String(unquote(value).to_string()),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+/// get a string field from front matter, TOML datetime will be converted to string.
+pub fn meta_string(meta: &HashMap<String, Value>, key: &str) -> Option<String> {
+    match meta.get(key) {
+        Some(Value::String(v)) => Some(v.clone()),
+        Some(Value::Datetime(v)) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_front_matter() {
+        let (meta, body) =
+            parse_front_matter("---\ntitle: \"Hello\"\ntags: [a, b]\nreleased: false\n---\nbody")
+                .unwrap();
+        assert_eq!(meta_string(&meta, "title").as_deref(), Some("Hello"));
+        assert_eq!(
+            meta.get("tags"),
+            Some(&Value::Array(vec![
+                Value::String("a".into()),
+                Value::String("b".into())
+            ]))
+        );
+        assert_eq!(meta.get("released"), Some(&Value::Boolean(false)));
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn yaml_block_list_and_wrapped_value() {
+        let content =
+            "---\ntitle: Post\ntags:\n  - a\n  - 'b'\ndescription: first\n  second\n---\n";
+        let (meta, _) = parse_front_matter(content).unwrap();
+        assert_eq!(
+            meta.get("tags"),
+            Some(&Value::Array(vec![
+                Value::String("a".into()),
+                Value::String("b".into())
+            ]))
+        );
+        assert_eq!(
+            meta_string(&meta, "description").as_deref(),
+            Some("first second")
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let (meta, _) =
+            parse_front_matter("---\ntitle: Post\nnot a pair\ndate: 2023-05-02\n---\n").unwrap();
+        assert_eq!(meta_string(&meta, "title").as_deref(), Some("Post"));
+        assert_eq!(meta_string(&meta, "date").as_deref(), Some("2023-05-02"));
+    }
+
+    #[test]
+    fn toml_front_matter() {
+        let (meta, body) =
+            parse_front_matter("\u{feff}+++\ntitle = \"Post\"\ndate = 2023-05-02\n+++\nbody")
+                .unwrap();
+        assert_eq!(meta_string(&meta, "title").as_deref(), Some("Post"));
+        assert_eq!(meta_string(&meta, "date").as_deref(), Some("2023-05-02"));
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn missing_or_unclosed_front_matter() {
+        assert!(parse_front_matter("# Title").is_none());
+        assert!(parse_front_matter("---\ntitle: Post\n").is_none());
+    }
+}
@@ -8,6 +8,7 @@ use dioxus::{
 use reqwasm::http::Request;
 pub use toml::Value;
 pub mod config;
+pub mod front_matter;
 
 #[derive(Debug, Props, PartialEq)]
 pub struct TemplateProps {
@@ -33,6 +34,8 @@ pub struct SharedUtility {
     pub navbar: fn(Scope) -> Element,
     /// giscus Component
     pub giscus: fn(Scope) -> Element,
+    /// latest commit date of current page content
+    pub last_updated: fn(Scope) -> Element,
     /// 404 not found template
     pub _404: fn(Scope) -> Element,
     /// error template
@@ -47,6 +50,12 @@ pub struct SharedUtility {
 pub struct ErrorProps {
     pub title: String,
     pub content: String,
+    /// HTTP status code of the failed request
+    #[props(!optional, default)]
+    pub status: Option<u16>,
+    /// extra detail (e.g. source excerpt), displayed in monospace
+    #[props(!optional, default)]
+    pub detail: Option<String>,
 }
 
 #[derive(Debug, Props, PartialEq)]
@@ -193,6 +202,8 @@ impl LazyLoader {
             return Err(ErrorProps {
                 title: "content load failed".to_string(),
                 content: format!("lazy loader load content `{}` failed.", url),
+                status: None,
+                detail: None,
             });
         } else {
             return Ok(resp.unwrap().text().await.unwrap());
@@ -8,6 +8,13 @@ use std::{
     process::Command,
 };
 
+#[path = "src/utils/feed.rs"]
+mod feed;
+#[path = "../blueprint/src/front_matter.rs"]
+mod front_matter;
+#[path = "src/utils/sitemap.rs"]
+mod sitemap;
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct Config {
     #[serde(default)]
@@ -21,6 +28,10 @@ struct BuildConfig {
     #[serde(default)]
     #[serde(rename = "static-generator")]
     pub static_gen: Option<StaticGenInfo>,
+    #[serde(default)]
+    pub feed: Option<FeedInfo>,
+    #[serde(default)]
+    pub sitemap: Option<SitemapInfo>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -29,6 +40,38 @@ struct StaticGenInfo {
     pub target: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct FeedInfo {
+    /// markdown posts directory
+    pub source: String,
+    /// site url, feed file will be placed in the site root
+    #[serde(rename = "site-url")]
+    pub site_url: String,
+    /// post link prefix, e.g. `https://example.com/blog`
+    #[serde(rename = "post-link")]
+    pub post_link: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// generate `atom.xml` with `feed.xml`
+    #[serde(default)]
+    pub atom: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct SitemapInfo {
+    /// site url, e.g. `https://example.com`
+    #[serde(rename = "site-url")]
+    pub site_url: String,
+    /// local content directory, dynamic routes are expanded by files in it
+    #[serde(default = "default_sitemap_source")]
+    pub source: String,
+}
+
+fn default_sitemap_source() -> String {
+    String::from("data")
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct CargoConfig {
     pub dependencies: HashMap<String, toml::Value>,
@@ -53,6 +96,12 @@ fn main() {
             }
             copy_dir(&from, &to);
         }
+        if let Some(feed) = build.feed {
+            generate_feed(&feed);
+        }
+        if let Some(info) = build.sitemap {
+            generate_sitemap(&info, &config.other);
+        }
     }
     fs::copy(&config_file, PathBuf::from("public").join("karaty.toml")).unwrap();
     let _ = copy_dir(
@@ -61,6 +110,121 @@ fn main() {
     );
 }
 
+fn generate_feed(info: &FeedInfo) {
+    let source = PathBuf::from(&info.source);
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    let mut items = vec![];
+    if let Ok(list) = fs::read_dir(&source) {
+        for entry in list.flatten() {
+            let path = entry.path();
+            if path.extension().map(|v| v != "md").unwrap_or(true) {
+                continue;
+            }
+            let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+            if name.starts_with('_') {
+                continue;
+            }
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            let Some((meta, _)) = front_matter::parse_front_matter(&content) else {
+                continue;
+            };
+            if let Some(toml::Value::Boolean(false)) = meta.get("released") {
+                continue;
+            }
+            if let Some(title) = front_matter::meta_string(&meta, "title") {
+                items.push(feed::FeedItem {
+                    title,
+                    link: format!("{}/{}", info.post_link.trim_end_matches('/'), name),
+                    date: front_matter::meta_string(&meta, "date").unwrap_or_default(),
+                    description: front_matter::meta_string(&meta, "excerpt")
+                        .or(front_matter::meta_string(&meta, "description"))
+                        .unwrap_or_default(),
+                });
+            }
+        }
+    }
+    feed::sort_items(&mut items);
+
+    let channel = feed::FeedChannel {
+        title: info.title.clone(),
+        link: info.site_url.clone(),
+        description: info.description.clone(),
+    };
+    fs::write(
+        PathBuf::from("public").join("feed.xml"),
+        feed::rss(&channel, &items),
+    )
+    .unwrap();
+    if info.atom {
+        fs::write(
+            PathBuf::from("public").join("atom.xml"),
+            feed::atom(&channel, &items),
+        )
+        .unwrap();
+    }
+}
+
+fn generate_sitemap(info: &SitemapInfo, config: &HashMap<String, toml::Value>) {
+    let content = PathBuf::from(&info.source);
+    println!("cargo:rerun-if-changed={}", content.display());
+    println!("cargo:rerun-if-changed=config/routing.toml");
+
+    // routing from `karaty.toml` & `config/routing.toml`
+    let mut routing = config
+        .get("routing")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let routing_file = fs::read_to_string("config/routing.toml").unwrap_or_default();
+    if let Ok(value) = toml::from_str::<toml::Table>(&routing_file) {
+        if let Some(list) = value.get("routing").and_then(|v| v.as_array()) {
+            routing.extend(list.iter().cloned());
+        }
+    }
+
+    let mut pages = vec![];
+    for route in routing.iter().filter_map(|v| v.as_table()) {
+        if route.contains_key("redirect") {
+            continue;
+        }
+        let Some(path) = route.get("path").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let file = route.get("file").and_then(|v| v.as_str());
+        let file_segment = route
+            .get("config")
+            .and_then(|v| v.get("file-segment"))
+            .and_then(|v| v.as_str());
+        pages.extend(sitemap::expand_route(path, file, file_segment, &content));
+    }
+    if let Some(file_map) = config.get("file-map").and_then(|v| v.as_table()) {
+        for (path, file) in file_map {
+            pages.extend(sitemap::expand_route(path, file.as_str(), None, &content));
+        }
+    }
+
+    let mut entries = pages
+        .into_iter()
+        .map(|(path, file)| {
+            let lastmod = file
+                .and_then(|v| fs::read_to_string(v).ok())
+                .and_then(|v| front_matter::parse_front_matter(&v))
+                .and_then(|(meta, _)| front_matter::meta_string(&meta, "date"))
+                .and_then(|v| sitemap::lastmod(&v));
+            sitemap::SitemapEntry { path, lastmod }
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries.dedup_by(|a, b| a.path == b.path);
+
+    fs::write(
+        PathBuf::from("public").join("sitemap.xml"),
+        sitemap::sitemap(&info.site_url, &entries),
+    )
+    .unwrap();
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct IndexStruct {
     r#type: String,
@@ -0,0 +1,68 @@
+// Karaty service worker: network first, fall back to cache when offline.
+// Enabled by `offline = true` in `[site]` config.
+
+const CACHE_NAME = "karaty-offline-v1";
+
+// app shell, other files are cached when they are fetched.
+// paths are relative to the worker, so it also works under `base-path`
+const APP_SHELL = ["./", "./index.html", "./karaty.toml"];
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE_NAME)
+      .then((cache) => cache.addAll(APP_SHELL))
+      .catch(() => {})
+      .then(() => self.skipWaiting())
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys
+            .filter((key) => key.startsWith("karaty-offline-") && key !== CACHE_NAME)
+            .map((key) => caches.delete(key))
+        )
+      )
+      .then(() => self.clients.claim())
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  // private content (token) is never stored
+  if (request.method !== "GET" || request.headers.has("Authorization")) {
+    return;
+  }
+  const url = new URL(request.url);
+  if (url.protocol !== "http:" && url.protocol !== "https:") {
+    return;
+  }
+  event.respondWith(
+    fetch(request)
+      .then((response) => {
+        // `304` is handled by the app (etag cache), only complete responses are stored
+        if (response.ok) {
+          const copy = response.clone();
+          caches.open(CACHE_NAME).then((cache) => cache.put(request, copy));
+        }
+        return response;
+      })
+      .catch(() =>
+        caches.match(request, { ignoreVary: true }).then((cached) => {
+          if (cached) {
+            return cached;
+          }
+          // every route is rendered by the app shell
+          if (request.mode === "navigate") {
+            return caches.match("./index.html").then((shell) => shell || caches.match("./"));
+          }
+          return Response.error();
+        })
+      )
+  );
+});
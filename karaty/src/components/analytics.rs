@@ -0,0 +1,60 @@
+use dioxus::prelude::*;
+use dioxus_retrouter::use_route;
+use gloo::events::EventListener;
+use web_sys::wasm_bindgen::JsCast;
+
+use crate::{
+    config::ExternalLinkConfig,
+    utils::{analytics, data::GlobalData},
+};
+
+/// record pageview when route changed, it should be placed in `Router`.
+pub fn PageviewTracker(cx: Scope) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+    let route = use_route(cx);
+    let path = route.url().path().to_string();
+
+    // manual tracking, every route (including the first one) records a pageview
+    use_effect(cx, (&path,), move |_| async move {
+        if let Some(config) = &global.config.analytics {
+            analytics::track_pageview(config);
+        }
+    });
+
+    None
+}
+
+/// record clicks of external links in the whole page (content, navbar & footer),
+/// it should be placed in `Router`. nothing is recorded when "Do Not Track" is enabled.
+pub fn OutboundTracker(cx: Scope) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+
+    // listener is removed when the hook is dropped
+    cx.use_hook(|| {
+        let config = global.config.analytics.clone()?;
+        let document = web_sys::window()?.document()?;
+        Some(EventListener::new(&document, "click", move |event| {
+            let href = event
+                .target()
+                .and_then(|v| v.dyn_into::<web_sys::Element>().ok())
+                .and_then(|v| v.closest("a[href]").ok().flatten())
+                .and_then(|v| v.get_attribute("href"));
+            let Some(href) = href else {
+                return;
+            };
+            if !ExternalLinkConfig::is_external(&href) || analytics::do_not_track() {
+                return;
+            }
+            // absolute link to the site itself is not outbound
+            let location = web_sys::window().and_then(|v| v.location().host().ok());
+            let host = web_sys::Url::new(&href).ok().map(|v| v.host());
+            if host.is_some() && host == location {
+                return;
+            }
+            // navigation is not prevented, tracking request is sent in background
+            analytics::track_outbound(&config, &href);
+        }))
+    });
+
+    None
+}
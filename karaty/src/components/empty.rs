@@ -0,0 +1,25 @@
+use dioxus::prelude::*;
+
+#[derive(Props)]
+pub struct EmptyPagesProps<'a> {
+    onretry: EventHandler<'a, ()>,
+}
+
+/// shown when no page was loaded from data source, `onretry` should reload the pages.
+pub fn EmptyPages<'a>(cx: Scope<'a, EmptyPagesProps<'a>>) -> Element {
+    cx.render(rsx! {
+        div { class: "px-3 py-2 text-sm text-gray-500 dark:text-gray-300",
+            p { class: "font-semibold text-gray-800 dark:text-gray-100", "No content was loaded." }
+            ul { class: "mt-1 list-disc list-inside text-xs",
+                li { "the data source in `karaty.toml` may be misconfigured" }
+                li { "the API of the service may be rate limited" }
+            }
+            button {
+                class: "mt-2 px-2 py-1 text-xs rounded-md border border-gray-300 dark:border-gray-600 \
+                hover:bg-gray-100 dark:hover:bg-purple-700",
+                onclick: move |_| cx.props.onretry.call(()),
+                "Retry"
+            }
+        }
+    })
+}
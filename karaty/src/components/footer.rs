@@ -1,23 +1,34 @@
-use crate::components::icon::Icon;
+use crate::components::{
+    icon::{ExternalIcon, Icon},
+    language::LanguageSwitcher,
+    theme::ThemeToggle,
+};
 use dioxus::prelude::*;
-use dioxus_free_icons::icons::fa_solid_icons;
 use dioxus_retrouter::Link;
 
-use crate::{
-    hooks::mode::{is_dark, mode},
-    utils::data::GlobalData,
-};
+use crate::{hooks::route::site_link, utils::data::GlobalData};
 
 pub fn Footer(cx: Scope) -> Element {
     let global = cx.consume_context::<GlobalData>().unwrap();
     let config = &global.config;
 
-    let content = config.footer.content.clone();
+    if !config.footer.enable {
+        return None;
+    }
 
-    let dark_mode = is_dark(&cx);
+    let content = config.footer.content.clone();
+    let external = &config.site.external_link;
+    let copyright = config.footer.copyright.clone().map(|copyright| {
+        rsx! {
+            div {
+                class: "mt-4 flex justify-center text-sm text-gray-500 dark:text-gray-400",
+                "{copyright}"
+            }
+        }
+    });
 
     cx.render(rsx! {
-        div {
+        div { class: "karaty-footer",
             content.iter().enumerate().map(|(i, data)| {
                 let m = if i == 0 { 8 } else { 4 };
                 rsx! {
@@ -26,6 +37,7 @@ pub fn Footer(cx: Scope) -> Element {
                         data.iter().map(|info| {
                             match info.clone() {
                                 crate::config::NavigationInfo::TextToPage { text, page } => {
+                                    let page = site_link(cx, &page);
                                     rsx! { 
                                         Link {
                                             class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200",
@@ -35,15 +47,22 @@ pub fn Footer(cx: Scope) -> Element {
                                     }
                                 },
                                 crate::config::NavigationInfo::TextToLink { text, link } => {                                    
+                                    let rel = external.rel(&link, "");
+                                    let icon = external.show_icon(&link);
                                     rsx! { 
                                         a {
                                             class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200",
                                             href: "{link}",
+                                            rel: "{rel}",
                                             "{text}"
+                                            if icon {
+                                                rsx! { ExternalIcon {} }
+                                            }
                                         }
                                     }
                                 },
                                 crate::config::NavigationInfo::IconToPage { icon, page } => {
+                                    let page = site_link(cx, &page);
                                     rsx! { 
                                         Link {
                                             class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200",
@@ -53,10 +72,12 @@ pub fn Footer(cx: Scope) -> Element {
                                     }
                                 },
                                 crate::config::NavigationInfo::IconToLink { icon, link } => {
+                                    let rel = external.rel(&link, "");
                                     rsx! { 
                                         a {
                                             class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200",
                                             href: "{link}",
+                                            rel: "{rel}",
                                             Icon {
                                                 name: icon
                                             }
@@ -66,28 +87,12 @@ pub fn Footer(cx: Scope) -> Element {
                                 crate::config::NavigationInfo::Feature { feature } => {
                                     if feature == "mode-switch".to_string() {
                                         rsx! {
-                                            a {
-                                                class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200",
-                                                href: "javascript:;",
-                                                onclick: move |_| {
-                                                    mode(&cx, !dark_mode);
-                                                    cx.needs_update();
-                                                },
-                                                if is_dark(&cx) {
-                                                    rsx! {
-                                                        dioxus_free_icons::Icon {
-                                                            icon: fa_solid_icons::FaSun
-                                                        }
-                                                    }
-                                                } else {
-                                                    rsx! {
-                                                        dioxus_free_icons::Icon {
-                                                            icon: fa_solid_icons::FaMoon
-                                                        }
-                                                    }
-                                                }
+                                            ThemeToggle {
+                                                class: "text-black dark:text-white hover:text-gray-800 dark:hover:text-gray-200".to_string(),
                                             }
                                         }
+                                    } else if feature == "language-switch" {
+                                        rsx! { LanguageSwitcher {} }
                                     } else {
                                         rsx! { span { "unknown feature" } }
                                     }
@@ -102,6 +107,7 @@ pub fn Footer(cx: Scope) -> Element {
                     }
                 }
             })
+            copyright
         }
         br {}
     })
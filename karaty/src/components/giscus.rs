@@ -53,6 +53,8 @@ pub fn Giscus(cx: Scope<GiscusProps>) -> Element {
             "data-category": "{cx.props.category}",
             "data-category-id": "{cx.props.category_id}",
             "data-mapping": "{cx.props.mapping}",
+            // page identifier, used by `specific` mapping
+            "data-term": "{cx.props.url}",
             "data-strict": "{strict}",
             "data-reactions-enabled": "{reactions}",
             "data-emit-metadata": "{emit_metadata}",
@@ -263,3 +263,10 @@ pub fn Icon(cx: Scope<IconProps>) -> Element {
 
     cx.render(icon)
 }
+
+/// small arrow displayed after links which leave the site.
+pub fn ExternalIcon(cx: Scope) -> Element {
+    cx.render(rsx! {
+        span { class: "ml-0.5 text-xs", "aria-hidden": "true", "↗" }
+    })
+}
@@ -0,0 +1,41 @@
+use dioxus::prelude::*;
+
+use crate::{
+    hooks::language::{current_language, use_set_language},
+    utils::data::GlobalData,
+};
+
+#[derive(Props, PartialEq)]
+pub struct LanguageSwitcherProps {
+    #[props(default)]
+    class: String,
+}
+
+/// content language select, nothing is displayed when `i18n` is not configured.
+pub fn LanguageSwitcher(cx: Scope<LanguageSwitcherProps>) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+    let current = current_language(cx);
+    let set_language = use_set_language(cx);
+
+    let i18n = global.config.i18n?;
+    let current = current.unwrap_or(i18n.default.clone());
+
+    let options = i18n.languages.iter().map(|lang| {
+        let name = i18n.display_name(lang);
+        let selected = *lang == current;
+        rsx! {
+            option { value: "{lang}", selected: selected, "{name}" }
+        }
+    });
+
+    let class = &cx.props.class;
+    cx.render(rsx! {
+        select {
+            class: "px-2 py-1 text-sm rounded-md bg-white dark:bg-gray-800 dark:text-gray-200 \
+            border border-gray-300 dark:border-gray-600 focus:outline-none {class}",
+            "aria-label": "Language",
+            onchange: move |evt| set_language(evt.value.clone()),
+            options
+        }
+    })
+}
@@ -1,12 +1,114 @@
+use std::collections::HashMap;
+
 use dioxus::prelude::*;
-use karaty_blueprint::RendererProps;
-use markdown::{mdast::Node, ParseOptions};
+use dioxus_free_icons::icons::fa_solid_icons;
+use karaty_blueprint::{RendererProps, Value};
+use markdown::mdast::Node;
+
+use crate::{
+    components::icon::{ExternalIcon, Icon},
+    config::ExternalLinkConfig,
+    hooks::route::site_link,
+    utils::{
+        data::{source_base_url, GlobalData},
+        emoji,
+        markdown::{
+            admonition, footnote_ids, footnotes, group_details, heading_ids, inline_html,
+            next_footnote_prefix, reading_time, resolve_relative_url, table_layout,
+            table_of_contents, Footnotes, MdastBlock, SharedRenderer, TableCell,
+        },
+        math, mermaid,
+        sanitize::{image_attribute, is_safe_url, sanitize_html_with},
+    },
+};
+
+/// file path (in data source) of the content which current page loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentPath(pub String);
+
+/// shared render state for all nodes in one markdown content.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownState {
+    pub heading_ids: HashMap<usize, String>,
+    pub config: HashMap<String, Value>,
+    /// data source base url, use for relative links & images.
+    pub base_url: Option<String>,
+    /// directory of current markdown file in data source.
+    pub base_dir: String,
+    pub external_link: ExternalLinkConfig,
+    pub footnotes: Footnotes,
+    /// unique prefix of footnote ids, several contents may be displayed in one page.
+    pub footnote_prefix: String,
+    /// display ```` ```mermaid ```` code blocks as diagrams.
+    pub mermaid: bool,
+}
+
+impl MarkdownState {
+    /// rewrite relative url to the raw file url in data source.
+    pub fn resolve_url(&self, url: &str) -> Option<String> {
+        let base = self.base_url.as_ref()?;
+        resolve_relative_url(base, &self.base_dir, url)
+    }
+
+    /// raw html in markdown is displayed as plain text unless `allow-html = true`,
+    /// returns whether embeds are allowed (`allow-embeds = true`) when it's enabled.
+    pub fn raw_html(&self) -> Option<bool> {
+        if !matches!(self.config.get("allow-html"), Some(Value::Boolean(true))) {
+            return None;
+        }
+        Some(matches!(
+            self.config.get("allow-embeds"),
+            Some(Value::Boolean(true))
+        ))
+    }
+}
 
-use crate::components::icon::Icon;
+/// top-level blocks rendered in the first frame, long content will be appended progressively.
+const INITIAL_BLOCKS: usize = 50;
+/// top-level blocks appended in every following frame.
+const BLOCK_CHUNK: usize = 100;
+
+fn is_footnote_definition(node: &&Node) -> bool {
+    matches!(node, Node::FootnoteDefinition(_))
+}
 
 pub fn Markdown(cx: Scope<RendererProps>) -> Element {
-    let mdast = markdown::to_mdast(&cx.props.content, &ParseOptions::gfm());
-    use_effect(&cx, (&cx.props.content,), |_| async {
+    let renderer = cx.consume_context::<SharedRenderer>().unwrap_or_default();
+    let parser = renderer.clone();
+    // content is parsed once, re-render (progressive rendering) uses the cached tree
+    let mdast = use_memo(cx, (&cx.props.content,), move |(content,)| {
+        parser.0.parse(&content)
+    });
+    // footnote definitions are displayed at the bottom, not in place
+    let total = match mdast {
+        Some(Node::Root(root)) => root
+            .children
+            .iter()
+            .filter(|v| !is_footnote_definition(v))
+            .count(),
+        _ => 0,
+    };
+    let footnote_prefix = cx.use_hook(next_footnote_prefix).clone();
+    let rendered = use_state(cx, || INITIAL_BLOCKS);
+    use_effect(cx, (&cx.props.content, &total), |(_, total)| {
+        to_owned![rendered];
+        async move {
+            let mut count = INITIAL_BLOCKS;
+            rendered.set(count);
+            while count < total {
+                // yield to the event loop, let the browser paint rendered blocks first
+                gloo::timers::future::TimeoutFuture::new(0).await;
+                // content changed, a new loop is running
+                if *rendered.current() != count {
+                    break;
+                }
+                count += BLOCK_CHUNK;
+                rendered.set(count);
+            }
+        }
+    });
+    let finished = *rendered.get() >= total;
+    use_effect(&cx, (&cx.props.content, rendered.get()), |_| async {
         let _ = js_sys::eval(&indoc::formatdoc! {"
             var list = document.getElementsByClassName('code-raw');
             setTimeout(() => {{
@@ -31,30 +133,267 @@ pub fn Markdown(cx: Scope<RendererProps>) -> Element {
             }}, 1);
         "});
     });
-    if let Ok(Node::Root(root)) = mdast {
-        let children = root.children;
+    let site = cx
+        .consume_context::<GlobalData>()
+        .map(|global| global.config.site);
+    let math_enabled = site.as_ref().map(|v| v.math).unwrap_or(false);
+    let mermaid_enabled = site.as_ref().map(|v| v.mermaid).unwrap_or(false);
+    // math & diagrams are rendered by scripts after content mounted
+    use_effect(
+        cx,
+        (&cx.props.content, rendered.get()),
+        move |_| async move {
+            if math_enabled {
+                math::render();
+            }
+            if mermaid_enabled {
+                mermaid::render();
+            }
+        },
+    );
+    // content is rendered after async loading, scroll to `#section` in url manually
+    use_effect(&cx, (&cx.props.content, &finished), |_| async {
+        let _ = js_sys::eval(&indoc::formatdoc! {"
+            setTimeout(() => {{
+                var hash = window.location.hash;
+                if (!hash || hash.length <= 1) {{
+                    return;
+                }}
+                var id = hash.substring(1);
+                try {{
+                    id = decodeURIComponent(id);
+                }} catch (e) {{}}
+                var target = document.getElementById(id);
+                if (target) {{
+                    target.scrollIntoView();
+                }}
+            }}, 1);
+        "});
+    });
+    if let Some(Node::Root(root)) = mdast {
+        let children = &root.children;
+        let config = cx.props.config.clone();
+
+        let reading = if let Some(Value::Boolean(true)) = config.get("reading-time") {
+            let wpm = match config.get("reading-speed") {
+                Some(Value::Integer(v)) if *v > 0 => *v as usize,
+                _ => 200,
+            };
+            Some(reading_time(&cx.props.content, wpm))
+        } else {
+            None
+        };
+
+        let toc = if let Some(Value::Boolean(true)) = config.get("toc") {
+            table_of_contents(children)
+        } else {
+            vec![]
+        };
+
+        let external_link = cx
+            .consume_context::<GlobalData>()
+            .map(|global| global.config.site.external_link)
+            .unwrap_or_default();
+        let (base_url, base_dir) = content_location(cx, &config);
+
+        let footnotes = footnotes(children);
+        let footnote_list = if finished && !footnotes.definitions.is_empty() {
+            let definitions = footnotes.definitions.clone();
+            let prefix = footnote_prefix.clone();
+            Some(rsx! {
+                FootnoteList {
+                    definitions: definitions,
+                    prefix: prefix,
+                }
+            })
+        } else {
+            None
+        };
+
+        cx.provide_context(MarkdownState {
+            heading_ids: heading_ids(children),
+            config,
+            base_url,
+            base_dir,
+            external_link,
+            footnotes,
+            footnote_prefix,
+            mermaid: mermaid_enabled,
+        });
+
         return cx.render(rsx! {
+            if let Some(minutes) = reading {
+                rsx! {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "{minutes} min read"
+                    }
+                }
+            }
+            if !toc.is_empty() {
+                rsx! {
+                    TableOfContents {
+                        list: toc,
+                    }
+                }
+            }
             MdastNode {
-                nodes: children,
+                nodes: children
+                    .iter()
+                    .filter(|v| !is_footnote_definition(v))
+                    .take(*rendered.get())
+                    .cloned()
+                    .collect(),
+            }
+            footnote_list
+        });
+    }
+    // renderer without mdast support, use its html output
+    if let Ok(html) = renderer.0.render(&cx.props.content) {
+        return cx.render(rsx! {
+            div {
+                dangerous_inner_html: "{html}",
             }
         });
     }
     None
 }
 
+/// data source base url & directory of current content file, use for relative links & images.
+fn content_location(cx: &ScopeState, config: &HashMap<String, Value>) -> (Option<String>, String) {
+    let base_url = cx
+        .consume_context::<GlobalData>()
+        .and_then(|global| source_base_url(&global.config).ok());
+    // directory templates pass the file path (relative to bound directory) by `path`
+    let mut file = cx
+        .consume_context::<ContentPath>()
+        .map(|v| v.0)
+        .unwrap_or_default();
+    if let Some(Value::String(path)) = config.get("path") {
+        file = format!("{}/{}", file.trim_end_matches('/'), path);
+    }
+    let base_dir = match file.rsplit_once('/') {
+        Some((dir, name)) if name.contains('.') => dir.to_string(),
+        Some(_) => file.clone(),
+        None if file.contains('.') => String::new(),
+        None => file.clone(),
+    };
+    (base_url, base_dir)
+}
+
+/// html content renderer, content is sanitized like raw html in markdown.
+pub fn Html(cx: Scope<RendererProps>) -> Element {
+    let config = &cx.props.config;
+    // `allow-html = false` displays html as plain text, `allow-embeds = true` enables embeds
+    if let Some(Value::Boolean(false)) = config.get("allow-html") {
+        return cx.render(rsx! {
+            pre { class: "text-left whitespace-pre-wrap", "{cx.props.content}" }
+        });
+    }
+    let embeds = matches!(config.get("allow-embeds"), Some(Value::Boolean(true)));
+    let (base_url, base_dir) = content_location(cx, config);
+    let html = sanitize_html_with(&cx.props.content, embeds, &|url| {
+        resolve_relative_url(base_url.as_ref()?, &base_dir, url)
+    });
+    cx.render(rsx! {
+        div {
+            dangerous_inner_html: "{html}",
+        }
+    })
+}
+
+#[component]
+pub fn TableOfContents(cx: Scope, list: Vec<(u8, String, String)>) -> Element {
+    let items = list.iter().map(|(level, text, id)| {
+        let indent = (*level as usize - 1) * 16;
+        rsx! {
+            li {
+                style: "padding-left: {indent}px",
+                a {
+                    class: "text-gray-600 dark:text-gray-300 hover:text-blue-700 dark:hover:text-blue-300",
+                    href: "#{id}",
+                    "{text}"
+                }
+            }
+        }
+    });
+    cx.render(rsx! {
+        nav {
+            class: "not-prose text-left text-sm my-4 p-4 rounded-md bg-gray-50 dark:bg-gray-800",
+            p {
+                class: "font-semibold mb-2 dark:text-white",
+                "Contents"
+            }
+            ul {
+                items
+            }
+        }
+    })
+}
+
 #[component]
 pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
-    let display = nodes.iter().map(|node| {
+    let state = cx.consume_context::<MarkdownState>().unwrap_or_default();
+    // raw html is displayed as plain text unless `allow-html = true`
+    let blocks = match state.raw_html() {
+        Some(_) => group_details(nodes),
+        None => nodes.iter().cloned().map(MdastBlock::Node).collect(),
+    };
+    let display = blocks.iter().map(|block| {
+        let node = match block {
+            MdastBlock::Node(node) => node,
+            MdastBlock::Details {
+                open,
+                summary,
+                children,
+            } => {
+                let summary = summary
+                    .as_ref()
+                    .map(|v| sanitize_html_with(v, false, &|url| state.resolve_url(url)))
+                    .unwrap_or("Details".to_string());
+                return rsx! {
+                    Details {
+                        open: *open,
+                        summary: summary,
+                        content: children.clone(),
+                    }
+                };
+            }
+        };
         let children = node.children();
         let children = if children.is_none() {
             vec![]
         } else {
             children.unwrap().clone()
         };
-        let embedded = rsx! {
-            MdastNode {
-                nodes: children,
+        // inline html tags are separated nodes, render the whole phrasing content as html
+        let inline = matches!(
+            node,
+            Node::Paragraph(_)
+                | Node::Heading(_)
+                | Node::TableCell(_)
+                | Node::Emphasis(_)
+                | Node::Strong(_)
+                | Node::Delete(_)
+                | Node::Link(_)
+        ) && children.iter().any(|v| matches!(v, Node::Html(_)));
+        let embedded = match state.raw_html() {
+            Some(embeds) if inline => {
+                let html = sanitize_html_with(&inline_html(&children), embeds, &|url| {
+                    state.resolve_url(url)
+                });
+                let html = emoji::replace_in_html(&html);
+                rsx! {
+                    span {
+                        dangerous_inner_html: "{html}",
+                    }
+                }
             }
+            _ => rsx! {
+                MdastNode {
+                    nodes: children,
+                }
+            },
         };
         if let Node::Text(text) = node {
             rsx! {
@@ -97,10 +436,14 @@ pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
                 }
             }
         } else if let Node::Link(link) = node {
-            let url = link.url.clone();
+            let url = if is_safe_url(&link.url, false) {
+                state.resolve_url(&link.url).unwrap_or(link.url.clone())
+            } else {
+                String::new()
+            };
             let title = link.title.clone().unwrap_or_default();
-            if &link.url[0..1] == "@" {
-                let url = &link.url[1..];
+            if link.url.starts_with('@') {
+                let url = site_link(cx, &link.url[1..]);
                 return rsx! {
                     dioxus_retrouter::Link {
                         to: "{url}",
@@ -108,48 +451,108 @@ pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
                     }
                 };
             } else {
+                let rel = state.external_link.rel(&url, "");
+                let icon = state.external_link.show_icon(&url);
                 return rsx! {
                     a {
                         href: "{url}",
                         title: "{title}",
+                        rel: "{rel}",
                         embedded
+                        if icon {
+                            rsx! { ExternalIcon {} }
+                        }
                     }
                 };
             }
         } else if let Node::Heading(h) = node {
             let depth = h.depth;
+            let id = h
+                .position
+                .as_ref()
+                .and_then(|p| state.heading_ids.get(&p.start.offset))
+                .cloned()
+                .unwrap_or_default();
+            let href = format!("#{id}");
+            let anchor = rsx! {
+                a {
+                    class: "ml-2 no-underline text-gray-400 opacity-0 group-hover:opacity-100 transition-opacity",
+                    href: "{href}",
+                    title: "Permalink",
+                    "#"
+                }
+            };
             match depth {
-                1 => rsx! { h1 { embedded } },
-                2 => rsx! { h2 { embedded } },
-                3 => rsx! { h3 { embedded } },
-                4 => rsx! { h4 { embedded } },
-                5 => rsx! { h5 { embedded } },
-                _ => rsx! { h6 { embedded } },
+                1 => rsx! { h1 { class: "group", id: "{id}", embedded, anchor } },
+                2 => rsx! { h2 { class: "group", id: "{id}", embedded, anchor } },
+                3 => rsx! { h3 { class: "group", id: "{id}", embedded, anchor } },
+                4 => rsx! { h4 { class: "group", id: "{id}", embedded, anchor } },
+                5 => rsx! { h5 { class: "group", id: "{id}", embedded, anchor } },
+                _ => rsx! { h6 { class: "group", id: "{id}", embedded, anchor } },
             }
         } else if let Node::Code(code) = node {
+            // rendered by mermaid after mounted, see `mermaid::render`
+            if state.mermaid && code.lang.as_deref() == Some("mermaid") {
+                return rsx! {
+                    div {
+                        class: "mermaid-diagram not-prose flex justify-center my-4 overflow-x-auto",
+                        "{code.value}"
+                    }
+                };
+            }
             let language = &code.lang;
             let value = &code.value;
+            let line_numbers = matches!(state.config.get("line-numbers"), Some(Value::Boolean(true)));
             rsx! {
                 Code {
                     text: value.clone(),
                     language: language.clone().unwrap_or_default(),
+                    line_numbers: line_numbers,
                 }
             }
-        } else if let Node::BlockQuote(_) = node {
+        } else if let Node::InlineMath(m) = node {
+            // rendered by KaTeX after mounted, see `math::render`
+            rsx! {
+                span {
+                    class: "math-inline",
+                    "{m.value}"
+                }
+            }
+        } else if let Node::Math(m) = node {
+            rsx! {
+                div {
+                    class: "math-display overflow-x-auto",
+                    "{m.value}"
+                }
+            }
+        } else if let Node::Blockquote(quote) = node {
+            if let Some((kind, content)) = admonition(&quote.children) {
+                return rsx! {
+                    Admonition { kind: kind, content: content }
+                };
+            }
             rsx! {
                 blockquote {
                     embedded
                 }
             }
         } else if let Node::Image(img) = node {
-            let url = &img.url;
+            let url = if is_safe_url(&img.url, true) {
+                state.resolve_url(&img.url).unwrap_or(img.url.clone())
+            } else {
+                String::new()
+            };
             let alt = &img.alt;
             let title = img.title.clone().unwrap_or_default();
+            let loading = image_attribute("loading").unwrap_or_default();
+            let decoding = image_attribute("decoding").unwrap_or_default();
             rsx! {
                 img {
                     src: "{url}",
                     alt: "{alt}",
                     title: "{title}",
+                    "loading": "{loading}",
+                    "decoding": "{decoding}",
                 }
             }
         } else if let Node::List(list) = node {
@@ -189,10 +592,32 @@ pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
                     }
                 }
             }
-        } else if let Node::Table(_) = node {
+        } else if let Node::Table(table) = node {
+            let layout = table_layout(table);
+            let head = layout.head.map(|cells| {
+                rsx! {
+                    thead {
+                        TableRow {
+                            cells: cells,
+                            header: true,
+                        }
+                    }
+                }
+            });
+            let body = layout.body.into_iter().map(|cells| {
+                rsx! {
+                    TableRow {
+                        cells: cells,
+                        header: false,
+                    }
+                }
+            });
             rsx! {
                 table {
-                    embedded
+                    head
+                    tbody {
+                        body
+                    }
                 }
             }
         } else if let Node::TableRow(_) = node {
@@ -208,12 +633,56 @@ pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
                 }
             }
         } else if let Node::Html(raw) = node {
+            // `<!--more-->` only marks the end of excerpt
+            if raw.value.trim() == "<!--more-->" {
+                return rsx! { embedded };
+            }
+            let Some(embeds) = state.raw_html() else {
+                return rsx! { span { "{raw.value}" } };
+            };
+            let html = sanitize_html_with(&raw.value, embeds, &|url| state.resolve_url(url));
             rsx! {
                 div {
                     class: "not-prose",
-                    dangerous_inner_html: "{raw.value}"
+                    dangerous_inner_html: "{html}"
                 }
             }
+        } else if let Node::FootnoteReference(r) = node {
+            let Some(number) = state.footnotes.numbers.get(&r.identifier) else {
+                return rsx! { "[^{r.identifier}]" };
+            };
+            let (id, ref_id) = footnote_ids(&state.footnote_prefix, *number);
+            let first = r
+                .position
+                .as_ref()
+                .map(|p| state.footnotes.first_refs.contains(&p.start.offset))
+                .unwrap_or(false);
+            // back link of the footnote points to its first reference
+            if first {
+                rsx! {
+                    sup {
+                        a {
+                            class: "no-underline",
+                            id: "{ref_id}",
+                            href: "#{id}",
+                            "[{number}]"
+                        }
+                    }
+                }
+            } else {
+                rsx! {
+                    sup {
+                        a {
+                            class: "no-underline",
+                            href: "#{id}",
+                            "[{number}]"
+                        }
+                    }
+                }
+            }
+        } else if let Node::FootnoteDefinition(_) = node {
+            // displayed by `FootnoteList` at the bottom
+            rsx! { "" }
         } else if let Node::Definition(_def) = node {
             // waiting for design
             rsx! { embedded }
@@ -228,6 +697,69 @@ pub fn MdastNode(cx: Scope, nodes: Vec<Node>) -> Element {
     })
 }
 
+/// collapsible `<details>` block, markdown content is rendered inside.
+#[component]
+pub fn Details(cx: Scope, open: bool, summary: String, content: Vec<Node>) -> Element {
+    cx.render(rsx! {
+        details {
+            class: "karaty-details my-4 px-4 py-2 rounded-md border border-gray-200 dark:border-gray-700 [&[open]>summary]:mb-2",
+            open: *open,
+            summary {
+                class: "cursor-pointer font-semibold",
+                dangerous_inner_html: "{summary}",
+            }
+            MdastNode { nodes: content.clone() }
+        }
+    })
+}
+
+/// footnote definitions at the bottom of content, numbered from 1.
+#[component]
+pub fn FootnoteList(cx: Scope, definitions: Vec<Vec<Node>>, prefix: String) -> Element {
+    let items = definitions.iter().enumerate().map(|(index, content)| {
+        let (id, ref_id) = footnote_ids(prefix, index + 1);
+        rsx! {
+            li {
+                id: "{id}",
+                class: "[&>p:last-of-type]:inline",
+                MdastNode { nodes: content.clone() }
+                " "
+                a {
+                    class: "no-underline",
+                    href: "#{ref_id}",
+                    title: "Back to reference",
+                    "↩"
+                }
+            }
+        }
+    });
+    cx.render(rsx! {
+        section {
+            class: "text-sm mt-8",
+            hr {}
+            ol {
+                items
+            }
+        }
+    })
+}
+
+#[component]
+pub fn TableRow(cx: Scope, cells: Vec<TableCell>, header: bool) -> Element {
+    let cells = cells.iter().map(|(children, style)| {
+        if *header {
+            rsx! { th { style: "{style}", MdastNode { nodes: children.clone() } } }
+        } else {
+            rsx! { td { style: "{style}", MdastNode { nodes: children.clone() } } }
+        }
+    });
+    cx.render(rsx! {
+        tr {
+            cells
+        }
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum TextFlag {
     Text(String),
@@ -236,7 +768,7 @@ pub enum TextFlag {
 
 #[component]
 pub fn Text(cx: Scope, value: String) -> Element {
-    let re = js_sys::RegExp::new("\\:([a-zA-Z0-9.-]+)\\:", "gi");
+    let re = js_sys::RegExp::new("\\:([a-zA-Z0-9._+-]+)\\:", "gi");
     let mut contents: Vec<TextFlag> = vec![];
     let mut latest_split_index = 0;
     while let Some(v) = re.exec(value) {
@@ -248,7 +780,15 @@ pub fn Text(cx: Scope, value: String) -> Element {
         contents.push(TextFlag::Text(
             value[latest_split_index..start_index].to_string(),
         ));
-        contents.push(TextFlag::Icon(icon.as_string().unwrap()));
+        let icon = icon.as_string().unwrap();
+        // emoji shortcode (`:tada:`) first, then icon name, otherwise keep the text
+        if let Some(emoji) = emoji::emoji(&icon) {
+            contents.push(TextFlag::Text(emoji.to_string()));
+        } else if icon.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-')) {
+            contents.push(TextFlag::Icon(icon));
+        } else {
+            contents.push(TextFlag::Text(format!(":{icon}:")));
+        }
         latest_split_index = last_index;
     }
     contents.push(TextFlag::Text(value[latest_split_index..].to_string()));
@@ -264,16 +804,111 @@ pub fn Text(cx: Scope, value: String) -> Element {
     cx.render(rsx! { display })
 }
 
+/// `> [!NOTE]` style callout box.
 #[component]
-pub fn Code(cx: Scope, text: String, language: String) -> Element {
+pub fn Admonition(cx: Scope, kind: String, content: Vec<Node>) -> Element {
+    let (class, title_class) = match kind.as_str() {
+        "tip" => (
+            "border-green-500 bg-green-50 dark:bg-green-900/20",
+            "text-green-700 dark:text-green-400",
+        ),
+        "important" => (
+            "border-purple-500 bg-purple-50 dark:bg-purple-900/20",
+            "text-purple-700 dark:text-purple-400",
+        ),
+        "warning" => (
+            "border-yellow-500 bg-yellow-50 dark:bg-yellow-900/20",
+            "text-yellow-700 dark:text-yellow-400",
+        ),
+        "caution" | "danger" => (
+            "border-red-500 bg-red-50 dark:bg-red-900/20",
+            "text-red-700 dark:text-red-400",
+        ),
+        _ => (
+            "border-blue-500 bg-blue-50 dark:bg-blue-900/20",
+            "text-blue-700 dark:text-blue-400",
+        ),
+    };
+    let icon = match kind.as_str() {
+        "tip" => rsx! { dioxus_free_icons::Icon { icon: fa_solid_icons::FaLightbulb } },
+        "important" => rsx! { dioxus_free_icons::Icon { icon: fa_solid_icons::FaCircleExclamation } },
+        "warning" => rsx! { dioxus_free_icons::Icon { icon: fa_solid_icons::FaTriangleExclamation } },
+        "caution" | "danger" => rsx! { dioxus_free_icons::Icon { icon: fa_solid_icons::FaBan } },
+        _ => rsx! { dioxus_free_icons::Icon { icon: fa_solid_icons::FaCircleInfo } },
+    };
+    let title = match kind.get(..1) {
+        Some(first) => format!("{}{}", first.to_uppercase(), &kind[1..]),
+        None => String::new(),
+    };
     cx.render(rsx! {
         div {
-            class: "not-prose",
+            class: "my-4 px-4 py-1 text-left border-l-4 rounded-r-md {class}",
+            p {
+                class: "flex items-center gap-2 font-semibold {title_class}",
+                icon
+                "{title}"
+            }
+            MdastNode {
+                nodes: content.clone(),
+            }
+        }
+    })
+}
+
+#[component]
+pub fn Code(cx: Scope, text: String, language: String, line_numbers: bool) -> Element {
+    let copied = use_state(cx, || false);
+    let label = if *copied.get() { "Copied!" } else { "Copy" };
+    // highlighted `pre` is appended by script, gutter sits beside it in a flex row,
+    // code lines don't wrap (scroll horizontally) so numbers stay aligned
+    let (class, gutter) = if *line_numbers {
+        let count = text.split('\n').count();
+        let numbers = (1..=count)
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        (
+            "not-prose relative flex [&>pre]:flex-1 [&>pre]:min-w-0",
+            Some(numbers),
+        )
+    } else {
+        ("not-prose relative", None)
+    };
+    cx.render(rsx! {
+        div {
+            class: "{class}",
             div {
                 class: "hidden code-raw",
                 code { "{text}" }
                 span { "{language}" }
             }
+            if let Some(numbers) = gutter {
+                rsx! {
+                    // `hljs` takes the background of current code theme
+                    div {
+                        class: "hljs select-none text-right text-sm font-mono whitespace-pre \
+                            !py-[1em] !pl-3 !pr-2 !text-gray-500 border-r border-gray-500/40",
+                        "aria-hidden": "true",
+                        "{numbers}"
+                    }
+                }
+            }
+            button {
+                class: "absolute top-2 right-2 text-xs px-2 py-1 rounded-md \
+                    bg-gray-200 text-gray-700 hover:bg-gray-300 \
+                    dark:bg-gray-700 dark:text-gray-200 dark:hover:bg-gray-600",
+                onclick: move |_| {
+                    let clipboard = web_sys::window().unwrap().navigator().clipboard();
+                    let _ = clipboard.write_text(text);
+                    copied.set(true);
+                    let copied = copied.clone();
+                    cx.spawn(async move {
+                        gloo::timers::future::TimeoutFuture::new(2000).await;
+                        copied.set(false);
+                    });
+                },
+                "{label}"
+            }
         }
     })
 }
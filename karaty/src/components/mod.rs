@@ -1,6 +1,15 @@
+pub mod analytics;
+pub mod empty;
 pub mod footer;
 pub mod icon;
+pub mod language;
 pub mod markdown;
 pub mod nav;
+pub mod prefetch;
 pub mod giscus;
 pub mod loading;
+pub mod search;
+pub mod spinner;
+pub mod theme;
+pub mod transition;
+pub mod updated;
@@ -1,42 +1,158 @@
+use std::collections::HashMap;
+
 pub use dioxus::prelude::*;
 use dioxus_retrouter::Link;
+use gloo::events::EventListener;
+use karaty_blueprint::Value;
+
+use crate::{
+    components::{
+        icon::{ExternalIcon, Icon},
+        language::LanguageSwitcher,
+        search::SearchBar,
+        theme::ThemeToggle,
+    },
+    config::{ExternalLinkConfig, NavigationInfo, NavigationItem},
+    hooks::route::{site_link, use_route_path},
+    utils::data::GlobalData,
+};
+
+/// navbar of current page, provided by `DynamicTemplate` from route config.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NavbarOverride {
+    /// `navbar = "minimal"`, only site brand is displayed
+    pub minimal: bool,
+    /// `navigation = [...]`, replace the global navigation content
+    pub content: Option<Vec<NavigationItem>>,
+}
+
+impl NavbarOverride {
+    pub fn from_config(config: &HashMap<String, Value>) -> Self {
+        let minimal = matches!(config.get("navbar"), Some(Value::String(v)) if v == "minimal");
+        let content = config.get("navigation").and_then(|v| {
+            v.clone()
+                .try_into::<Vec<NavigationItem>>()
+                .map_err(|e| log::warn!("invalid `navigation` in route config: {}", e))
+                .ok()
+        });
+        Self { minimal, content }
+    }
+}
 
-use crate::{components::icon::Icon, config::NavigationInfo, utils::data::GlobalData};
+/// scroll distance (px) before the transparent navbar becomes opaque.
+const NAVBAR_OPAQUE_OFFSET: f64 = 16.0;
+
+const NAVBAR_BACKGROUND: &str = "bg-gray-100 dark:bg-purple-900";
 
 pub fn Navbar(cx: Scope) -> Element {
     let data = cx.consume_context::<GlobalData>().unwrap();
     let config = data.config;
-    let nav = config.navigation.content.clone();
+    let nav_override = cx.consume_context::<NavbarOverride>().unwrap_or_default();
+    let nav = if nav_override.minimal {
+        vec![]
+    } else {
+        nav_override
+            .content
+            .unwrap_or(config.navigation.content.clone())
+    };
+    // items are placed at the right side unless `align = "left"`
+    let (left_nav, right_nav): (Vec<NavigationItem>, Vec<NavigationItem>) =
+        nav.iter().cloned().partition(|item| item.is_left());
+    let mode = config.navigation.mode.clone().unwrap_or_default();
+    let transparent = mode == "transparent";
 
     let mobile_navbar = use_state(&cx, || false);
 
+    let scrolled = use_state(cx, || {
+        web_sys::window()
+            .and_then(|v| v.scroll_y().ok())
+            .unwrap_or_default()
+            > NAVBAR_OPAQUE_OFFSET
+    });
+    // only the transparent navbar needs to follow the scroll position
+    cx.use_hook(|| {
+        if !transparent {
+            return None;
+        }
+        let window = web_sys::window()?;
+        let scrolled = scrolled.clone();
+        let target = window.clone();
+        Some(EventListener::new(&target, "scroll", move |_| {
+            let value = window.scroll_y().unwrap_or_default() > NAVBAR_OPAQUE_OFFSET;
+            if value != *scrolled.current() {
+                scrolled.set(value);
+            }
+        }))
+    });
+
+    // minimal navbar has nothing to expand
+    let menu_button_class = if nav.is_empty() {
+        "hidden"
+    } else {
+        "sm:hidden absolute right-0"
+    };
+
+    let nav_class = match mode.as_str() {
+        "sticky" => format!("sticky top-0 z-40 shadow-sm {NAVBAR_BACKGROUND}"),
+        // opened mobile menu needs a background to be readable
+        "transparent" if *scrolled.get() || *mobile_navbar.get() => {
+            format!("fixed top-0 inset-x-0 z-40 shadow-sm transition-colors duration-300 {NAVBAR_BACKGROUND}")
+        }
+        "transparent" => {
+            "fixed top-0 inset-x-0 z-40 bg-transparent transition-colors duration-300".to_string()
+        }
+        _ => NAVBAR_BACKGROUND.to_string(),
+    };
+
     cx.render(rsx! {
-        nav { class: "bg-gray-100 dark:bg-purple-900",
+        nav { class: "{nav_class}",
             div { class: "max-w-7xl mx-auto px-2 sm:px-6 lg:px-8",
-                div { class: "sm:relative flex items-center justify-between h-16",
+                div { class: "relative flex items-center justify-between h-16",
                     div { class: "flex-1 flex items-center justify-center sm:items-stretch sm:justify-start",
                         div { class: "hidden sm:block",
-                            Link {
-                                class: "flex-shrink-0 flex items-center font-bold text-2xl dark:text-white",
-                                to: "/",
-                                "{config.site.name}"
-                            }
+                            SiteBrand {}
                         }
                         div { class: "sm:hidden",
+                            SiteBrand {}
+                        }
+                        if !left_nav.is_empty() {
+                            rsx! {
+                                div { class: "hidden sm:flex sm:ml-6 items-center space-x-4",
+                                    left_nav.iter().map(|v| {
+                                        rsx! {
+                                            NavItemMiddle { value: v.info.clone() }
+                                        }
+                                    })
+                                }
+                            }
+                        }
+                        div { class: "{menu_button_class}",
                             a {
-                                class: "flex-shrink-0 flex items-center font-bold text-2xl dark:text-white",
+                                class: "p-2 flex items-center text-gray-800 dark:text-gray-200",
                                 href: "javascript:;",
                                 onclick: move |_| {
                                     mobile_navbar.set(!mobile_navbar.get());
                                 },
-                                "{config.site.name}"
+                                if *mobile_navbar.get() {
+                                    rsx! {
+                                        dioxus_free_icons::Icon {
+                                            icon: dioxus_free_icons::icons::fa_solid_icons::FaXmark
+                                        }
+                                    }
+                                } else {
+                                    rsx! {
+                                        dioxus_free_icons::Icon {
+                                            icon: dioxus_free_icons::icons::fa_solid_icons::FaBars
+                                        }
+                                    }
+                                }
                             }
                         }
                         div { class: "hidden sm:block sm:ml-6 absolute right-0",
                             div { class: "flex space-x-4",
-                                nav.iter().map(|v| {
+                                right_nav.iter().map(|v| {
                                     rsx! {
-                                        NavItemMiddle { value: v.clone() }
+                                        NavItemMiddle { value: v.info.clone() }
                                     }
                                 })
                             }
@@ -46,9 +162,9 @@ pub fn Navbar(cx: Scope) -> Element {
                 if *mobile_navbar.get() {
                     rsx! {
                         div { class: "sm:hidden",
-                            div { class: "flex flex-col bg-gray-100 dark:bg-purple-900 rounded-lg",
+                            div { class: "flex flex-col {NAVBAR_BACKGROUND} rounded-lg",
                                 nav.iter().map(|v| {
-                                    rsx! { NavItemMobile { value: v.clone() } }
+                                    rsx! { NavItemMobile { value: v.info.clone() } }
                                 })
                             }
                         }
@@ -56,76 +172,137 @@ pub fn Navbar(cx: Scope) -> Element {
                 }
             }
         }
-        br {}
+        // transparent navbar is placed over the page top
+        if !transparent {
+            rsx! { br {} }
+        }
     })
 }
 
+/// site logo and/or site name, link to home page.
+pub fn SiteBrand(cx: Scope) -> Element {
+    let config = cx.consume_context::<GlobalData>().unwrap().config;
+    let site = config.site;
+    let home = site.with_base(&config.navigation.home());
+    let name = (site.logo.is_none() || !site.hide_name).then(|| site.name.clone());
+    let class = "flex-shrink-0 flex items-center gap-2 font-bold text-2xl dark:text-white";
+    let alt = site.name.clone();
+    let logo = site.logo.as_ref().map(|logo| site.with_base(logo)).map(|logo| {
+        rsx! {
+            img { class: "h-8 w-auto", src: "{logo}", alt: "{alt}" }
+        }
+    });
+    let name = name.map(|name| {
+        rsx! { span { "{name}" } }
+    });
+    // home outside of the app (e.g. parent site) is a plain link
+    if ExternalLinkConfig::is_external(&home) {
+        cx.render(rsx! {
+            a { class: "{class}", href: "{home}", logo, name }
+        })
+    } else {
+        cx.render(rsx! {
+            Link { class: "{class}", to: "{home}", logo, name }
+        })
+    }
+}
+
+/// check the nav page is current route, `/guide` is active for `/guide/intro`.
+fn is_active(current: &str, page: &str) -> bool {
+    let current = current.trim_end_matches('/');
+    let page = page.trim_end_matches('/');
+    if page.is_empty() {
+        return current.is_empty();
+    }
+    current == page || current.starts_with(&format!("{page}/"))
+}
+
+/// nav item contains current route (collection will check all children).
+fn nav_item_active(current: &str, value: &NavigationInfo) -> bool {
+    match value {
+        NavigationInfo::TextToPage { page, .. } | NavigationInfo::IconToPage { page, .. } => {
+            is_active(current, page)
+        }
+        NavigationInfo::Collection { list, page, .. } => {
+            page.as_ref().map(|page| is_active(current, page)).unwrap_or(false)
+                || list.iter().any(|item| nav_item_active(current, item))
+        }
+        _ => false,
+    }
+}
+
 #[component]
 pub fn NavItemMiddle(cx: Scope, value: NavigationInfo) -> Element {
+    let external = cx
+        .consume_context::<GlobalData>()
+        .map(|v| v.config.site.external_link)
+        .unwrap_or_default();
+    let current = use_route_path(cx);
     let link_class = "text-gray-800 dark:text-gray-200 hover:bg-gray-700 hover:text-white px-3 py-2 rounded-md text-sm font-medium";
-    let dark_mode = crate::hooks::mode::is_dark(&cx);
+    let active_class = if nav_item_active(&current, value) {
+        format!("{link_class} bg-gray-300 dark:bg-gray-800")
+    } else {
+        link_class.to_string()
+    };
     let display = match value {
         NavigationInfo::TextToPage { text, page } => {
+            let page = site_link(cx, page);
             rsx! {
                 Link {
-                    class: "{link_class}",
+                    class: "{active_class}",
                     to: "{page}",
                     "{text}"
                 }
             }
         }
         NavigationInfo::TextToLink { text, link } => {
+            let rel = external.rel(link, "");
+            let icon = external.show_icon(link);
             rsx! {
                 a {
                     class: "{link_class}",
                     href: "{link}",
+                    rel: "{rel}",
                     "{text}"
+                    if icon {
+                        rsx! { ExternalIcon {} }
+                    }
                 }
             }
         }
         NavigationInfo::IconToPage { icon, page } => {
+            let page = site_link(cx, page);
             rsx! {
                 Link {
-                    class: "{link_class}",
+                    class: "{active_class}",
                     to: "{page}",
                     Icon { name: icon.to_string() }
                 }
             }
         }
         NavigationInfo::IconToLink { icon, link } => {
+            let rel = external.rel(link, "");
             rsx! {
                 a {
                     class: "{link_class}",
                     href: "{link}",
+                    rel: "{rel}",
                     Icon { name: icon.to_string() }
                 }
             }
         }
         NavigationInfo::Feature { feature } => {
             if feature.as_str() == "mode-switch" {
-                let icon = if crate::hooks::mode::is_dark(&cx) {
-                    rsx! {
-                        dioxus_free_icons::Icon {
-                            icon: dioxus_free_icons::icons::fa_solid_icons::FaSun
-                        }
-                    }
-                } else {
-                    rsx! {
-                        dioxus_free_icons::Icon {
-                            icon: dioxus_free_icons::icons::fa_solid_icons::FaMoon
-                        }
-                    }
-                };
                 rsx! {
-                    a {
-                        class: "{link_class}",
-                        href: "javascript:;",
-                        onclick: move |_| {
-                            crate::hooks::mode::mode(&cx, !dark_mode);
-                            cx.needs_update();
-                        },
-                        icon
-                    }
+                    ThemeToggle { class: link_class.to_string() }
+                }
+            } else if feature.as_str() == "search" {
+                rsx! {
+                    SearchBar { class: "flex items-center".to_string() }
+                }
+            } else if feature.as_str() == "language-switch" {
+                rsx! {
+                    LanguageSwitcher { class: "flex items-center".to_string() }
                 }
             } else {
                 rsx! {
@@ -143,11 +320,12 @@ pub fn NavItemMiddle(cx: Scope, value: NavigationInfo) -> Element {
                 }
             }
         }
-        NavigationInfo::Collection { text, list } => {
+        NavigationInfo::Collection { text, list, page } => {
             rsx! {
                 NavItemDropdown {
                     text: text.clone(),
                     list: list.clone(),
+                    page: page.clone(),
                 }
             }
         }
@@ -160,21 +338,58 @@ pub fn NavItemMiddle(cx: Scope, value: NavigationInfo) -> Element {
 }
 
 #[component]
-pub fn NavItemDropdown(cx: Scope, text: String, list: Vec<NavigationInfo>) -> Element {
+pub fn NavItemDropdown(
+    cx: Scope,
+    text: String,
+    list: Vec<NavigationInfo>,
+    #[props(!optional)] page: Option<String>,
+) -> Element {
     let dropdown = use_state(&cx, || false);
+    let current = use_route_path(cx);
+    let active = page.as_ref().map(|page| is_active(&current, page)).unwrap_or(false)
+        || list.iter().any(|item| nav_item_active(&current, item));
+    let active_class = if active {
+        "bg-gray-300 dark:bg-gray-800"
+    } else {
+        ""
+    };
     let li = list
         .iter()
         .map(|v| rsx! { NavItemMiddle { value: v.clone() } });
+    let title = match page {
+        Some(page) => {
+            let page = site_link(cx, page);
+            rsx! {
+                Link {
+                    class: "text-gray-800 dark:text-gray-200 text-sm font-medium",
+                    to: "{page}",
+                    "{text}"
+                }
+            }
+        }
+        None => rsx! {
+            span {
+                class: "text-gray-800 dark:text-gray-200 text-sm font-medium",
+                "{text}"
+            }
+        },
+    };
     cx.render(rsx! {
         div {
-            class: "px-3 py-2 hover:bg-gray-300 dark:hover:bg-gray-800 rounded-lg flex justify-center items-center",
+            class: "relative px-3 py-2 hover:bg-gray-300 dark:hover:bg-gray-800 rounded-lg flex justify-center items-center {active_class}",
+            onmouseenter: move |_| {
+                dropdown.set(true);
+            },
+            onmouseleave: move |_| {
+                dropdown.set(false);
+            },
+            title,
             a {
-                class: "text-gray-800 dark:text-gray-200 text-sm font-medium",
+                class: "text-gray-800 dark:text-gray-200",
                 href: "javascript:;",
                 onclick: move |_| {
                     dropdown.set(!dropdown.get());
                 },
-                "{text}"
                 dioxus_free_icons::Icon {
                     class: "inline-block ml-1",
                     height: 14,
@@ -185,7 +400,10 @@ pub fn NavItemDropdown(cx: Scope, text: String, list: Vec<NavigationInfo>) -> El
             if *dropdown.get() {
                 rsx! {
                     div {
-                        class: "absolute top-8 bg-white rounded-lg shadow dark:bg-purple-800",
+                        class: "absolute top-full left-0 z-10 min-w-max bg-white rounded-lg shadow dark:bg-purple-800",
+                        onclick: move |_| {
+                            dropdown.set(false);
+                        },
                         div {
                             class: "p-2 flex flex-col",
                             li
@@ -199,70 +417,76 @@ pub fn NavItemDropdown(cx: Scope, text: String, list: Vec<NavigationInfo>) -> El
 
 #[component]
 pub fn NavItemMobile(cx: Scope, value: NavigationInfo) -> Element {
+    let external = cx
+        .consume_context::<GlobalData>()
+        .map(|v| v.config.site.external_link)
+        .unwrap_or_default();
+    let current = use_route_path(cx);
     let link_class = "m-2 font-semibold dark:text-gray-200 flex justify-center";
-    let dark_mode = crate::hooks::mode::is_dark(&cx);
+    let active_class = if nav_item_active(&current, value) {
+        format!("{link_class} underline underline-offset-4")
+    } else {
+        link_class.to_string()
+    };
     let display = match value {
         NavigationInfo::TextToPage { text, page } => {
+            let page = site_link(cx, page);
             rsx! {
                 Link {
-                    class: "{link_class}",
+                    class: "{active_class}",
                     to: "{page}",
                     "{text}"
                 }
             }
         }
         NavigationInfo::TextToLink { text, link } => {
+            let rel = external.rel(link, "");
+            let icon = external.show_icon(link);
             rsx! {
                 a {
                     class: "{link_class}",
                     href: "{link}",
+                    rel: "{rel}",
                     "{text}"
+                    if icon {
+                        rsx! { ExternalIcon {} }
+                    }
                 }
             }
         }
         NavigationInfo::IconToPage { icon, page } => {
+            let page = site_link(cx, page);
             rsx! {
                 Link {
-                    class: "{link_class}",
+                    class: "{active_class}",
                     to: "{page}",
                     Icon { name: icon.to_string() }
                 }
             }
         }
         NavigationInfo::IconToLink { icon, link } => {
+            let rel = external.rel(link, "");
             rsx! {
                 a {
                     class: "{link_class}",
                     href: "{link}",
+                    rel: "{rel}",
                     Icon { name: icon.to_string() }
                 }
             }
         }
         NavigationInfo::Feature { feature } => {
             if feature.as_str() == "mode-switch" {
-                let icon = if crate::hooks::mode::is_dark(&cx) {
-                    rsx! {
-                        dioxus_free_icons::Icon {
-                            icon: dioxus_free_icons::icons::fa_solid_icons::FaSun
-                        }
-                    }
-                } else {
-                    rsx! {
-                        dioxus_free_icons::Icon {
-                            icon: dioxus_free_icons::icons::fa_solid_icons::FaMoon
-                        }
-                    }
-                };
                 rsx! {
-                    a {
-                        class: "{link_class}",
-                        href: "javascript:;",
-                        onclick: move |_| {
-                            crate::hooks::mode::mode(&cx, !dark_mode);
-                            cx.needs_update();
-                        },
-                        icon
-                    }
+                    ThemeToggle { class: link_class.to_string() }
+                }
+            } else if feature.as_str() == "search" {
+                rsx! {
+                    SearchBar { class: "m-2".to_string() }
+                }
+            } else if feature.as_str() == "language-switch" {
+                rsx! {
+                    LanguageSwitcher { class: "m-2".to_string() }
                 }
             } else {
                 rsx! {
@@ -280,11 +504,12 @@ pub fn NavItemMobile(cx: Scope, value: NavigationInfo) -> Element {
                 }
             }
         }
-        NavigationInfo::Collection { text, list } => {
+        NavigationInfo::Collection { text, list, page } => {
             rsx! {
                 NavItemDropdownMobile {
                     text: text.clone(),
                     list: list.clone(),
+                    page: page.clone(),
                 }
             }
         }
@@ -297,10 +522,26 @@ pub fn NavItemMobile(cx: Scope, value: NavigationInfo) -> Element {
 }
 
 #[component]
-pub fn NavItemDropdownMobile(cx: Scope, text: String, list: Vec<NavigationInfo>) -> Element {
+pub fn NavItemDropdownMobile(
+    cx: Scope,
+    text: String,
+    list: Vec<NavigationInfo>,
+    #[props(!optional)] page: Option<String>,
+) -> Element {
     let dropdown = use_state(&cx, || false);
-    let ls = list.iter().map(|v| {
-        rsx! { NavItemMobile { value: v.clone() } }
+    // collection page will be the first item of expanded list
+    let mut list = list.clone();
+    if let Some(page) = page {
+        list.insert(
+            0,
+            NavigationInfo::TextToPage {
+                text: text.clone(),
+                page: page.clone(),
+            },
+        );
+    }
+    let ls = list.into_iter().map(|v| {
+        rsx! { NavItemMobile { value: v } }
     });
     cx.render(rsx! {
         div {
@@ -0,0 +1,62 @@
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use gloo::{events::EventListener, timers::callback::Timeout};
+use web_sys::wasm_bindgen::JsCast;
+
+use crate::{
+    hooks::language::current_language,
+    utils::{data::GlobalData, prefetch},
+};
+
+/// hover time (ms) before the linked page is prefetched.
+const PREFETCH_DELAY: u32 = 100;
+
+/// prefetch content of internal links when they are hovered, it should be placed in `Router`.
+pub fn LinkPrefetcher(cx: Scope) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+
+    let language = use_ref(cx, || None::<String>);
+    let current = current_language(cx);
+    if *language.read() != current {
+        *language.write_silent() = current;
+    }
+
+    // links are loaded one by one, so the same file is never requested twice at the same time
+    let loader = use_coroutine(cx, |mut rx: UnboundedReceiver<String>| {
+        to_owned![global, language];
+        async move {
+            while let Some(path) = rx.next().await {
+                let path = global.config.site.strip_base(&path);
+                let Some(file) = prefetch::page_file(&global.config, &global.routing, &path)
+                else {
+                    continue;
+                };
+                let lang = language.read().clone();
+                prefetch::prefetch(&global.config, &file, lang.as_deref()).await;
+            }
+        }
+    });
+
+    let timer = use_ref(cx, || None::<Timeout>);
+    cx.use_hook(|| {
+        let document = web_sys::window()?.document()?;
+        let loader = loader.clone();
+        let timer = timer.clone();
+        Some(EventListener::new(&document, "mouseover", move |event| {
+            let link = event
+                .target()
+                .and_then(|v| v.dyn_into::<web_sys::Element>().ok())
+                .and_then(|v| v.closest("a[href^='/']").ok().flatten())
+                .and_then(|v| v.get_attribute("href"));
+            let Some(link) = link else {
+                return;
+            };
+            let path = link.split(['?', '#']).next().unwrap_or_default().to_string();
+            let loader = loader.clone();
+            // replacing the timer cancels the pending one (pointer moved to another link)
+            timer.set(Some(Timeout::new(PREFETCH_DELAY, move || loader.send(path))));
+        }))
+    });
+
+    None
+}
@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use dioxus_retrouter::Link;
+
+use gloo::timers::callback::Timeout;
+
+use crate::{components::empty::EmptyPages, hooks::route::site_link, utils::data::GlobalData};
+
+/// delay (ms) after the last keystroke before results are updated.
+const SEARCH_DEBOUNCE: u32 = 200;
+
+#[derive(Props, PartialEq)]
+pub struct SearchBarProps {
+    #[props(default)]
+    class: String,
+}
+
+pub fn SearchBar(cx: Scope<SearchBarProps>) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+    // `query` is the input value, `keyword` follows it after typing paused
+    let query = use_state(cx, String::new);
+    let keyword = use_state(cx, String::new);
+    let timer = use_ref(cx, || None::<Timeout>);
+    let ready = use_state(cx, || global.search_index.borrow().is_some());
+    let loading = use_state(cx, || false);
+
+    let results = if keyword.get().trim().is_empty() {
+        vec![]
+    } else {
+        global
+            .search_index
+            .borrow()
+            .as_ref()
+            .map(|index| index.search(keyword.get(), 8))
+            .unwrap_or_default()
+    };
+
+    // nothing could be loaded (misconfigured data source or rate limited)
+    let empty = *ready.get() && !*loading.get() && global.pages.borrow().is_empty();
+
+    let status = if empty {
+        None
+    } else if !*ready.get() {
+        Some("Loading search index...")
+    } else if results.is_empty() {
+        Some("No results found.")
+    } else {
+        None
+    };
+    let status = status.map(|text| {
+        rsx! {
+            p { class: "px-3 py-2 text-sm text-gray-500 dark:text-gray-300", "{text}" }
+        }
+    });
+
+    let loader = global.clone();
+    let load = &*cx.bump().alloc(move || {
+        loading.set(true);
+        let global = loader.clone();
+        let ready = ready.clone();
+        let loading = loading.clone();
+        cx.spawn(async move {
+            global.load_search_index().await;
+            ready.set(true);
+            loading.set(false);
+        });
+    });
+
+    let empty = empty.then(|| {
+        rsx! {
+            EmptyPages {
+                onretry: move |_| {
+                    global.reset_search_index();
+                    ready.set(false);
+                    load();
+                },
+            }
+        }
+    });
+
+    let list = results.iter().map(|item| {
+        let link = site_link(cx, &item.link);
+        rsx! {
+            div {
+                class: "px-3 py-2 rounded-md hover:bg-gray-100 dark:hover:bg-purple-700",
+                onclick: move |_| {
+                    timer.set(None);
+                    query.set(String::new());
+                    keyword.set(String::new());
+                },
+                Link {
+                    to: "{link}",
+                    p { class: "text-sm font-semibold text-gray-800 dark:text-gray-100", "{item.title}" }
+                    p { class: "text-xs text-gray-500 dark:text-gray-300 truncate", "{item.excerpt}" }
+                }
+            }
+        }
+    });
+
+    let class = &cx.props.class;
+    cx.render(rsx! {
+        div { class: "relative {class}",
+            input {
+                class: "w-full sm:w-48 px-3 py-1 text-sm rounded-md bg-white dark:bg-gray-800 \
+                dark:text-gray-200 border border-gray-300 dark:border-gray-600 focus:outline-none",
+                r#type: "search",
+                placeholder: "Search...",
+                value: "{query}",
+                onfocus: move |_| {
+                    if !*ready.get() && !*loading.get() {
+                        load();
+                    }
+                },
+                oninput: move |evt| {
+                    let value = evt.value.clone();
+                    query.set(value.clone());
+                    // replacing the timer drops (cancels) the pending one
+                    let keyword = keyword.clone();
+                    timer.set(Some(Timeout::new(SEARCH_DEBOUNCE, move || keyword.set(value))));
+                },
+            }
+            if !query.get().trim().is_empty() && !keyword.get().trim().is_empty() {
+                rsx! {
+                    div {
+                        class: "absolute right-0 z-10 mt-2 w-72 p-2 bg-white rounded-lg shadow dark:bg-purple-800",
+                        list
+                        status
+                        empty
+                    }
+                }
+            }
+        }
+    })
+}
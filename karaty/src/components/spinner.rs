@@ -0,0 +1,23 @@
+use dioxus::prelude::*;
+
+#[derive(Props, PartialEq)]
+pub struct SpinnerProps {
+    #[props(default)]
+    class: String,
+}
+
+/// centered loading spinner, use for content fetching.
+pub fn Spinner(cx: Scope<SpinnerProps>) -> Element {
+    let class = &cx.props.class;
+    cx.render(rsx! {
+        div {
+            class: "flex items-center justify-center w-full {class}",
+            div {
+                class: "h-10 w-10 rounded-full border-4 border-gray-200 border-t-gray-700 \
+                animate-spin dark:border-gray-700 dark:border-t-gray-200",
+                role: "status",
+                "aria-label": "loading",
+            }
+        }
+    })
+}
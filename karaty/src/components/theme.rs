@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::fa_solid_icons;
+
+use crate::{
+    hooks::mode::{is_dark, mode},
+    utils::{data::GlobalData, head},
+};
+
+const CODE_THEME_ID: &str = "karaty-code-theme";
+
+#[derive(Props, PartialEq)]
+pub struct ThemeToggleProps {
+    #[props(default)]
+    class: String,
+}
+
+pub fn ThemeToggle(cx: Scope<ThemeToggleProps>) -> Element {
+    let dark_mode = is_dark(&cx);
+    let class = &cx.props.class;
+    cx.render(rsx! {
+        a {
+            class: "{class}",
+            href: "javascript:;",
+            onclick: move |_| {
+                mode(&cx, !dark_mode);
+                cx.needs_update();
+            },
+            if dark_mode {
+                rsx! {
+                    dioxus_free_icons::Icon {
+                        icon: fa_solid_icons::FaSun
+                    }
+                }
+            } else {
+                rsx! {
+                    dioxus_free_icons::Icon {
+                        icon: fa_solid_icons::FaMoon
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// load the code highlight stylesheet of current color mode, see `site.code-theme`.
+pub fn CodeTheme(cx: Scope) -> Element {
+    let site = cx.consume_context::<GlobalData>().unwrap().config.site;
+    let href = site.with_base(&site.code_theme.stylesheet(is_dark(cx)));
+    use_effect(cx, (&href,), |(href,)| async move {
+        head::set_stylesheet(CODE_THEME_ID, &href);
+    });
+    None
+}
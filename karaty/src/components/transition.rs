@@ -0,0 +1,60 @@
+use dioxus::prelude::*;
+
+/// delay (ms) before the entering transition starts, let the initial state be painted first.
+const MOUNT_DELAY: u32 = 20;
+
+#[derive(Props)]
+pub struct PageTransitionProps<'a> {
+    /// `fade` or `slide`, children are rendered directly without transition.
+    #[props(!optional)]
+    kind: Option<String>,
+    /// transition runs again when the path changed
+    path: String,
+    children: Element<'a>,
+}
+
+/// page enter transition, animation is disabled by `prefers-reduced-motion`.
+pub fn PageTransition<'a>(cx: Scope<'a, PageTransitionProps<'a>>) -> Element<'a> {
+    let visible = use_state(cx, || false);
+    use_effect(cx, (&cx.props.path,), |_| {
+        to_owned![visible];
+        async move {
+            visible.set(false);
+            gloo::timers::future::TimeoutFuture::new(MOUNT_DELAY).await;
+            visible.set(true);
+        }
+    });
+
+    let class = match cx.props.kind.as_deref() {
+        Some("fade") => {
+            let state = if *visible.get() {
+                "opacity-100"
+            } else {
+                "opacity-0"
+            };
+            format!(
+                "transition-opacity duration-300 ease-out motion-reduce:transition-none {state}"
+            )
+        }
+        Some("slide") => {
+            let state = if *visible.get() {
+                "opacity-100 translate-y-0"
+            } else {
+                "opacity-0 translate-y-4"
+            };
+            format!(
+                "transition duration-300 ease-out motion-reduce:transition-none \
+                motion-reduce:transform-none {state}"
+            )
+        }
+        _ => {
+            return cx.render(rsx! { &cx.props.children });
+        }
+    };
+    cx.render(rsx! {
+        div {
+            class: "{class}",
+            &cx.props.children
+        }
+    })
+}
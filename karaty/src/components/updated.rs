@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+use karaty_template::format_date;
+
+use crate::{
+    components::markdown::ContentPath,
+    utils::data::{load_last_updated, GlobalData},
+};
+
+/// latest commit date of current page content, enabled by `last-updated = true` route config.
+/// nothing is displayed when the date is unavailable (e.g. not a GitHub data source).
+pub fn LastUpdated(cx: Scope) -> Element {
+    let global = cx.consume_context::<GlobalData>().unwrap();
+    let path = cx
+        .consume_context::<ContentPath>()
+        .map(|v| v.0)
+        .unwrap_or_default();
+    let config = global.config.clone();
+    let date = use_future(cx, (&path,), |(path,)| async move {
+        if path.is_empty() {
+            return None;
+        }
+        load_last_updated(&config, &path).await
+    });
+
+    let Some(Some(date)) = date.value() else {
+        return None;
+    };
+    let date = format_date(date, global.config.site.date_format.as_deref());
+    cx.render(rsx! {
+        p { class: "mt-6 text-sm text-gray-500 dark:text-gray-400", "Last updated: {date}" }
+    })
+}
+
@@ -0,0 +1,54 @@
+use dioxus::core::ScopeState;
+use fermi::{use_read, use_set, Atom};
+
+use crate::config::Config;
+
+/// active content language, `None` when `i18n` is not configured.
+pub static LANGUAGE: Atom<Option<String>> = Atom(|_| None);
+
+const STORAGE_KEY: &str = "language";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn set_document_language(lang: &str) {
+    let _ = js_sys::eval(&format!(
+        "document.documentElement.lang = {};",
+        serde_json::to_string(lang).unwrap_or_default()
+    ));
+}
+
+pub fn current_language(cx: &ScopeState) -> Option<String> {
+    use_read(cx, &LANGUAGE).clone()
+}
+
+/// returns a setter which switches the active language and persists it.
+pub fn use_set_language(cx: &ScopeState) -> impl Fn(String) + '_ {
+    let set_language = use_set(cx, &LANGUAGE);
+    move |lang: String| {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(STORAGE_KEY, &lang);
+        }
+        set_document_language(&lang);
+        set_language(Some(lang));
+    }
+}
+
+/// restore the stored language, fall back to the default language when it's unavailable.
+pub fn init_language(cx: &ScopeState, config: &Config) {
+    let set_language = use_set(cx, &LANGUAGE).clone();
+    let i18n = config.i18n.clone();
+    // only initialize once, app re-render must not reset the user choice
+    cx.use_hook(move || {
+        let Some(i18n) = i18n else {
+            return;
+        };
+        let stored = local_storage().and_then(|v| v.get_item(STORAGE_KEY).ok().flatten());
+        let lang = stored
+            .filter(|v| i18n.languages.contains(v))
+            .unwrap_or(i18n.default);
+        set_document_language(&lang);
+        set_language(Some(lang));
+    });
+}
@@ -1 +1,3 @@
+pub mod language;
 pub mod mode;
+pub mod route;
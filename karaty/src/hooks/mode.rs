@@ -23,7 +23,14 @@ pub fn mode(cx: &ScopeState, dark: bool) {
 pub fn init_mode_info(cx: &ScopeState) {
     let storage = use_local_storage(cx);
     let v = cx.use_hook(move || {
-        let dark = storage.get("mode").unwrap_or("light".to_string()) == "dark";
+        let dark = match storage.get("mode") {
+            Some(mode) => mode == "dark",
+            // follow system preference when user not choose mode
+            None => js_sys::eval("window.matchMedia('(prefers-color-scheme: dark)').matches")
+                .ok()
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
         if dark {
             let _ = js_sys::eval("document.documentElement.classList.add('dark');");
         } else {
@@ -0,0 +1,21 @@
+use dioxus::core::ScopeState;
+use dioxus_retrouter::use_route;
+
+use crate::utils::data::GlobalData;
+
+/// current route path without `site.base-path`, e.g. `/docs` for `/my-site/docs`.
+pub fn use_route_path(cx: &ScopeState) -> String {
+    let path = use_route(cx).url().path().to_string();
+    match cx.consume_context::<GlobalData>() {
+        Some(global) => global.config.site.strip_base(&path),
+        None => path,
+    }
+}
+
+/// link target of an internal page, `site.base-path` is prepended to absolute path.
+pub fn site_link(cx: &ScopeState, path: &str) -> String {
+    match cx.consume_context::<GlobalData>() {
+        Some(global) => global.config.site.with_base(path),
+        None => path.to_string(),
+    }
+}
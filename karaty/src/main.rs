@@ -16,7 +16,9 @@ mod pages;
 
 use setup::{setup_config, setup_root_app};
 use utils::{
-    data::{load_routing_file, load_template_file, GlobalData},
+    data::{
+        apply_file_map, expand_nested_routing, load_routing_file, load_template_file, GlobalData,
+    },
     template_loader,
 };
 
@@ -37,13 +39,16 @@ fn App(cx: Scope) -> Element {
             let mut routing = config.routing.clone();
 
             // load content from config directory
-            let routing_ext = load_routing_file("/config/routing.toml")
+            let routing_ext = load_routing_file(&config.site.with_base("/config/routing.toml"))
                 .await
                 .unwrap_or_default();
             routing.extend(routing_ext);
-            let template_config = load_template_file("/config/template.toml")
-                .await
-                .unwrap_or_default();
+            let routing = apply_file_map(routing, &config.file_map);
+            let routing = expand_nested_routing(routing);
+            let template_config =
+                load_template_file(&config.site.with_base("/config/template.toml"))
+                    .await
+                    .unwrap_or_default();
 
             // load custom template list
             let templates = template_loader::loader();
@@ -53,6 +58,8 @@ fn App(cx: Scope) -> Element {
                 routing,
                 template_config,
                 templates,
+                pages: Default::default(),
+                search_index: Default::default(),
             })
         });
 
@@ -60,76 +67,94 @@ fn App(cx: Scope) -> Element {
         Some(Ok(data)) => {
             let _ = setup_root_app(&cx, data.clone());
 
-            cx.render(rsx! {
-                // dioxus toast manager init
-                ToastFrame {
-                    manager: fermi::use_atom_ref(&cx, &TOAST_MANAGER),
+            let base_path = data.config.site.base_path();
+            let routes = rsx! {
+                if data.config.analytics.is_some() {
+                    rsx! { components::analytics::PageviewTracker {} }
                 }
-                // dioxus router info
-                Router {
-
-                    data.routing.iter().map(|v| {
-                        match v {
-                            RoutingInfo::FileBind { path, file, template, config } => {
-                                    let config = {
-                                        if config.is_none() {
-                                            HashMap::new()
-                                        } else {
-                                            let config = config.clone().unwrap();
-                                            let config = config.as_table();
-                                            config.map(|v| {
-                                                let mut t = HashMap::new();
-                                                for i in v {
-                                                   t.insert(i.0.clone(), i.1.clone());
-                                                }
-                                                t
-                                            }).unwrap_or_default()
-                                        }
-                                    };
-                                    rsx! {
-                                        Route {
-                                            to: "{path}",
-                                            DynamicTemplate {
-                                                path: path.to_string(),
-                                                name: file.to_string(),
-                                                template: template.clone(),
-                                                file: file.clone(),
-                                                config: config,
+                if data.config.analytics.as_ref().map(|v| v.outbound).unwrap_or(false) {
+                    rsx! { components::analytics::OutboundTracker {} }
+                }
+                components::theme::CodeTheme {}
+                if data.config.site.prefetch {
+                    rsx! { components::prefetch::LinkPrefetcher {} }
+                }
+
+                data.routing.iter().map(|v| {
+                    match v {
+                        RoutingInfo::FileBind { path, file, template, config } => {
+                                let config = {
+                                    if config.is_none() {
+                                        HashMap::new()
+                                    } else {
+                                        let config = config.clone().unwrap();
+                                        let config = config.as_table();
+                                        config.map(|v| {
+                                            let mut t = HashMap::new();
+                                            for i in v {
+                                               t.insert(i.0.clone(), i.1.clone());
                                             }
-                                        }
+                                            t
+                                        }).unwrap_or_default()
                                     }
-                            }
-                            RoutingInfo::RedirectBind { path, redirect } => {
+                                };
                                 rsx! {
                                     Route {
                                         to: "{path}",
-                                        div {
-                                            class: "h-screen flex justify-center items-center",
-                                            p {
-                                                class: "text-gray-500 text-3xl font-semibold",
-                                                "Redirect..."
-                                            }
+                                        DynamicTemplate {
+                                            path: path.to_string(),
+                                            name: file.to_string(),
+                                            template: template.clone(),
+                                            file: file.clone(),
+                                            config: config,
                                         }
-                                        dioxus_retrouter::Redirect {
-                                            to: "{redirect}"
+                                    }
+                                }
+                        }
+                        RoutingInfo::RedirectBind { path, redirect } => {
+                            let redirect_to = data.config.site.with_base(redirect);
+                            rsx! {
+                                Route {
+                                    to: "{path}",
+                                    div {
+                                        class: "h-screen flex justify-center items-center",
+                                        p {
+                                            class: "text-gray-500 text-3xl font-semibold",
+                                            "Redirect..."
                                         }
                                     }
+                                    dioxus_retrouter::Redirect {
+                                        to: "{redirect_to}"
+                                    }
                                 }
                             }
                         }
-                    })
-
-                    Route {
-                        to: "/_test",
-                        div {
-                            crate::components::markdown::Markdown {
-                                content: "hello **dioxus**!".to_string(),
-                                config: Default::default(),
-                            }
+                    }
+                })
+
+                Route {
+                    to: "/_test",
+                    div {
+                        crate::components::markdown::Markdown {
+                            content: "hello **dioxus**!".to_string(),
+                            config: Default::default(),
                         }
                     }
+                }
 
-                    Route { to: "", pages::error::PageNotFound {} }
+                Route { to: "", pages::error::PageNotFound {} }
+            };
+
+            cx.render(rsx! {
+                // dioxus toast manager init
+                ToastFrame {
+                    manager: fermi::use_atom_ref(&cx, &TOAST_MANAGER),
+                }
+                // dioxus router info, `base_url` is only set for sub path deployment
+                if base_path.is_empty() {
+                    rsx! { Router { routes } }
+                } else {
+                    rsx! { Router { base_url: "{base_path}", routes } }
                 }
             })
         }
@@ -138,7 +163,7 @@ fn App(cx: Scope) -> Element {
                 div {
                     class: "h-screen flex justify-center items-center",
                     p {
-                        class: "text-gray-400 text-xl font-semibold",
+                        class: "text-gray-400 text-xl font-semibold whitespace-pre-line",
                         "{e}"
                     }
                 }
@@ -1,19 +1,134 @@
-use crate::components::{footer::Footer, nav::Navbar};
+use crate::{
+    components::{footer::Footer, nav::Navbar, spinner::Spinner},
+    hooks::{
+        language::current_language,
+        route::{site_link, use_route_path},
+    },
+    utils::data::{load_from_source, GlobalData},
+};
 use dioxus::prelude::*;
-use karaty_blueprint::ErrorProps;
+use dioxus_retrouter::Link;
+use karaty_blueprint::{ErrorProps, TemplateData, TemplateDataType, TemplateRouteData};
 
+use super::template::shared_utility;
+
+/// error page in the navbar & footer shell, copy & style can be changed by `[error-page]` config.
 pub fn Error(cx: Scope<ErrorProps>) -> Element {
-    let title = &cx.props.title;
+    let config = cx
+        .consume_context::<GlobalData>()
+        .map(|v| v.config.error_page)
+        .unwrap_or_default();
+    let title = config.title(&cx.props.title);
     let content = &cx.props.content;
+    let status = cx.props.status.map(|status| {
+        rsx! {
+            div { class: "flex justify-center", p { class: "text-gray-400 text-xl font-semibold", "Status: {status}" } }
+        }
+    });
+    let detail = cx.props.detail.as_ref().map(|detail| {
+        rsx! {
+            div { class: "flex justify-center mt-6",
+                pre { class: "max-w-full overflow-x-auto p-4 rounded-md bg-gray-100 text-gray-700 text-sm text-left", "{detail}" }
+            }
+        }
+    });
+    let body = config.body.as_ref().map(|body| {
+        rsx! {
+            div { class: "flex justify-center mt-6", p { class: "text-gray-500 dark:text-gray-400", "{body}" } }
+        }
+    });
+    let report = config.report_link.as_ref().map(|link| {
+        let text = config
+            .report_text
+            .clone()
+            .unwrap_or_else(|| "Report this problem".to_string());
+        rsx! {
+            div { class: "flex justify-center mt-4",
+                a {
+                    class: "text-blue-600 dark:text-blue-400 hover:underline",
+                    href: "{link}",
+                    target: "_blank",
+                    rel: "noopener noreferrer",
+                    "{text}"
+                }
+            }
+        }
+    });
+    let class = config.class.clone().unwrap_or_default();
     return cx.render(rsx! {
-        div { class: "h-screen",
-            div { class: "flex justify-center", p { class: "text-gray-600 text-4xl font-bold", "{title}" } }
-            div { class: "flex justify-center", p { class: "text-gray-500 text-3xl font-semibold", "{content}" } }
+        Navbar {}
+        section { class: "min-h-[calc(100vh-100px)] bg-white dark:bg-gray-900 {class}",
+            div { class: "container mx-auto px-8 py-16",
+                status
+                div { class: "flex justify-center", p { class: "text-gray-600 dark:text-gray-200 text-4xl font-bold", "{title}" } }
+                div { class: "flex justify-center", p { class: "text-gray-500 dark:text-gray-300 text-3xl font-semibold", "{content}" } }
+                detail
+                body
+                report
+            }
+            div { class: "flex justify-center container mx-auto my-14", Footer {} }
         }
     });
 }
 
+/// `site.not-found` file will be rendered by `center` template,
+/// built-in page is used when it's not configured or failed to load.
 pub fn PageNotFound(cx: Scope) -> Element {
+    let global = cx.consume_context::<GlobalData>();
+    let access_path = use_route_path(cx);
+    let home = site_link(cx, "/");
+    let config = global.as_ref().map(|v| v.config.clone());
+    let source = config.as_ref().and_then(|v| v.site.not_found.clone());
+    let language = current_language(cx);
+    let content = use_future(cx, (&source, &language), |(source, language)| async move {
+        let (Some(config), Some(source)) = (config, source) else {
+            return None;
+        };
+        load_from_source(
+            &config,
+            source.trim_start_matches('/'),
+            language.as_deref(),
+            false,
+        )
+            .await
+            .map_err(|e| log::warn!("load 404 page `{}` failed: {}", source, e))
+            .ok()
+    });
+
+    if source.is_some() {
+        let center = global.as_ref().and_then(|global| {
+            global
+                .templates
+                .get("karaty_template")
+                .and_then(|v| v.load("center", TemplateDataType::Markdown))
+                .map(|v| (*v, shared_utility(&global.config)))
+        });
+        match (content.value(), center) {
+            (None, _) => {
+                return cx.render(rsx! {
+                    Spinner { class: "h-[100vh] bg-white dark:bg-gray-900".to_string() }
+                });
+            }
+            (Some(Some(content)), Some((Center, utility))) => {
+                let path = TemplateRouteData {
+                    bound_path: String::new(),
+                    access_path: access_path.clone(),
+                    segments: Default::default(),
+                    queries: Default::default(),
+                };
+                return cx.render(rsx! {
+                    Center {
+                        route: path,
+                        data: TemplateData::File(content.clone()),
+                        utility: utility,
+                        config: Default::default(),
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+
     cx.render(rsx! {
         Navbar {}
         section { class: "h-[calc(100vh-100px)] bg-cover bg-white dark:bg-gray-900",
@@ -22,6 +137,11 @@ pub fn PageNotFound(cx: Scope) -> Element {
                     h1 { class: "text-3xl sm:text-5xl capitalize tracking-widest dark:text-white lg:text-6xl",
                         "Page Not Found"
                     }
+                    Link {
+                        class: "inline-block mt-6 px-4 py-2 rounded-md bg-gray-700 text-white hover:bg-gray-900",
+                        to: "{home}",
+                        "Go Home"
+                    }
                     Footer {}
                 }
             }
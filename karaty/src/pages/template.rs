@@ -1,10 +1,26 @@
 use crate::{
-    components::{footer::Footer, giscus::GiscusWithConfig, loading::Loading, markdown::Markdown, nav::Navbar},
-    utils::data::GlobalData,
+    components::{
+        footer::Footer,
+        giscus::GiscusWithConfig,
+        markdown::{ContentPath, Html, Markdown},
+        nav::{Navbar, NavbarOverride},
+        spinner::Spinner,
+        transition::PageTransition,
+        updated::LastUpdated,
+    },
+    config::Config,
+    hooks::{language::current_language, route::use_route_path},
+    utils::{
+        data::{DataError, GlobalData, MAX_NESTED_DEPTH},
+        head,
+    },
 };
 use dioxus::prelude::*;
 use dioxus_retrouter::use_route;
-use karaty_blueprint::{SharedUtility, TemplateData, TemplateDataType, TemplateRouteData, Value};
+use karaty_blueprint::{
+    front_matter::{meta_string, parse_front_matter},
+    SharedUtility, TemplateData, TemplateDataType, TemplateRouteData, Value,
+};
 use regex::Regex;
 use std::{collections::HashMap, path::PathBuf};
 
@@ -23,11 +39,44 @@ pub struct DynamicTemplateProps {
 pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
     let global = cx.consume_context::<GlobalData>().unwrap();
     let route = use_route(&cx);
+    cx.provide_context(NavbarOverride::from_config(&cx.props.config));
 
     let bind_path = cx.props.path.clone();
-    let access_path = route.url().path();
+    // location path without `site.base-path`
+    let access_path = use_route_path(cx);
+    let access_path = access_path.as_str();
 
-    let file_path: Vec<&str> = cx.props.file.split('/').collect();
+    let page_title = match cx.props.config.get("title") {
+        Some(Value::String(title)) => format!("{}{}", title, global.config.site.title_suffix),
+        _ => global.config.site.name.clone(),
+    };
+    let page_description = match cx.props.config.get("description") {
+        Some(Value::String(description)) => Some(description.clone()),
+        _ => global.config.site.description.clone(),
+    };
+    let page_image = match cx.props.config.get("image") {
+        Some(Value::String(image)) => Some(image.clone()),
+        _ => global.config.site.image.clone(),
+    };
+    use_effect(
+        cx,
+        (&page_title, &page_description, &page_image),
+        |(title, description, image)| async move {
+            head::set_title(&title);
+            match &description {
+                Some(description) => head::set_meta("name", "description", description),
+                None => head::remove_meta("name", "description"),
+            }
+            head::set_social_meta(&title, description.as_deref(), image.as_deref());
+        },
+    );
+
+    // `file-map` config can bind the access path to another file
+    let mapped_file = global.config.file_map.get(normalize_path(access_path));
+    let file = mapped_file.unwrap_or(&cx.props.file).clone();
+    let name = mapped_file.unwrap_or(&cx.props.name).clone();
+
+    let file_path: Vec<&str> = file.split('/').collect();
     let application_config = global.config.clone();
     let file_path = { 
         let mut path = String::new();
@@ -36,49 +85,62 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
             let re = Regex::new(r"\{([^}]*)\}").unwrap();
             for value in re.captures_iter(i) {
                 let sign = &value[1];
-                let seg = route.segment(&sign);
+                let seg = nested_segment(&bind_path, access_path, sign);
                 if let Some(seg) = seg {
-                    name = name.replace(&format!("{{{sign}}}"), seg);
+                    name = name.replace(&format!("{{{sign}}}"), &seg);
                 }
             }
             path.push_str(&format!("/{name}"));
         }
         path
     };
-    let data = use_future(&cx, (), |_| async move {
-        let mut file_path = file_path.clone();
+    let language = current_language(cx);
+    let data = use_future(cx, (&file_path, &language), |(mut file_path, language)| async move {
         if file_path.starts_with('/') {
             let mut bp = file_path.into_bytes();
             bp.remove(0);
             file_path = String::from_utf8(bp).unwrap();
         }
         if PathBuf::from(&file_path).extension().is_some() {
-            let v = crate::utils::data::load_from_source(&application_config, &file_path).await;
-            v.map(|v| TemplateData::File(v))
+            let v = crate::utils::data::load_page_file(
+                &application_config,
+                &file_path,
+                language.as_deref(),
+            )
+            .await;
+            v.map(|(path, v)| (path, TemplateData::File(v)))
         } else {
-            let dirs = crate::utils::data::load_content_list(&application_config, &file_path).await;
+            let dirs =
+                crate::utils::data::load_content_list(&application_config, &file_path).await?;
             let dirs = dirs
                 .iter()
                 .map(|v| (v.0.clone(), format!("{file_path}/{}", v.1)))
                 .collect();
-            let dir = crate::utils::data::load_page_from_dir(&application_config, dirs).await;
-            dir
+            let dir = crate::utils::data::load_page_from_dir(&application_config, dirs, 0).await;
+            dir.map(|v| (file_path, v)).map_err(DataError::from)
         }
     });
-    match data.value() {
-        Some(Ok(data)) => {
+    // content will be fetched again when the bound file changed (e.g. route segment)
+    let value = match data.state() {
+        UseFutureState::Complete(value) => Some(value),
+        _ => None,
+    };
+    match value {
+        Some(Ok((loaded_path, data))) => {
 
             let data = data.clone();
+            // index file may be loaded instead, relative links are based on it
+            cx.provide_context(ContentPath(loaded_path.clone()));
 
             let global = cx.consume_context::<GlobalData>().unwrap();
             let template_config = global.template_config;
 
             let suffix = {
-                let name = &cx.props.name;
+                let name = &name;
                 if name.is_empty() {
                     ""
                 } else if PathBuf::from(name).extension().is_some() {
-                    cx.props.name.split(".").last().unwrap()
+                    name.split(".").last().unwrap()
                 } else {
                     "#dir"
                 }
@@ -99,7 +161,7 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
 
             let templates = global.templates;
 
-            let using_component = {
+            let load_component = |template: &str| {
                 let mut namespace: Vec<&str> = template.split("::").collect();
                 let module = if namespace.len() == 1 {
                     templates.get("karaty_template").unwrap()
@@ -121,22 +183,26 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
                 value
             };
 
+            // `layout` in markdown front matter takes precedence over the routing template
+            let layout = match &data {
+                TemplateData::File(content) if suffix == "md" => parse_front_matter(content)
+                    .and_then(|(meta, _)| meta_string(&meta, "layout"))
+                    .filter(|v| !v.is_empty()),
+                _ => None,
+            };
+            let layout_component = layout.and_then(|layout| {
+                let component = load_component(&layout);
+                if component.is_none() {
+                    log::warn!("layout `{}` of `{}` is not found, skipped.", layout, loaded_path);
+                }
+                component
+            });
+            let using_component = layout_component.or_else(|| load_component(&template));
+
             if let Some(using_component) = using_component {
                 let using_component = using_component.clone();
 
-                let mut renderers: HashMap<String, fn(Scope<karaty_blueprint::RendererProps>) -> Element> =
-                    HashMap::new();
-                renderers.insert("markdown".to_string(), Markdown);
-
-                let utility = SharedUtility {
-                    navbar: Navbar,
-                    footer: Footer,
-                    giscus: GiscusWithConfig,
-                    _404: PageNotFound,
-                    error: Error,
-                    renderers,
-                    app_config: global.config.clone(),
-                };
+                let utility = shared_utility(&global.config);
 
                 let index_list = bind_path
                     .to_string()
@@ -146,10 +212,13 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
                     .map(|segment| segment[1..].to_string())
                     .collect::<Vec<String>>();
                 let mut segments = HashMap::new();
-                for i in index_list {
-                    let value = route.segment(&i);
+                for i in index_list.iter() {
+                    if is_nested_segment(&index_list, i) {
+                        continue;
+                    }
+                    let value = nested_segment(&bind_path, access_path, i);
                     if let Some(value) = value {
-                        segments.insert(i, value.to_string());
+                        segments.insert(i.clone(), value);
                     }
                 }
 
@@ -164,13 +233,18 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
                     queries,
                 };
 
+                let transition = global.config.site.transition.clone();
                 cx.render(rsx! {
                     div {
-                        using_component {
-                            route: path,
-                            data: data,
-                            utility: utility,
-                            config: cx.props.config.clone(),
+                        PageTransition {
+                            kind: transition,
+                            path: access_path.to_string(),
+                            using_component {
+                                route: path,
+                                data: data,
+                                utility: utility,
+                                config: cx.props.config.clone(),
+                            }
                         }
                     }
                 })
@@ -180,11 +254,92 @@ pub fn DynamicTemplate(cx: Scope<DynamicTemplateProps>) -> Element {
                 })
             }
         },
-        Some(Err(err)) => cx.render(rsx! { format!("{:?}", err) }),
+        Some(Err(DataError::NotFound(_))) => cx.render(rsx! {
+            PageNotFound {}
+        }),
+        Some(Err(err)) => {
+            let status = match err {
+                DataError::Status { status, .. } => Some(*status),
+                _ => None,
+            };
+            let title = match err {
+                DataError::Timeout { .. } => "Content Timed Out",
+                DataError::RateLimited { .. } => "Rate Limited",
+                DataError::Network(_) => "Network Error",
+                DataError::Parse(_) => "Invalid Content",
+                DataError::Config(_) => "Data Source Misconfigured",
+                _ => "Content Load Failed",
+            };
+            cx.render(rsx! {
+                Error {
+                    title: title.to_string(),
+                    content: err.to_string(),
+                    status: status,
+                }
+            })
+        }
         None => {
             return cx.render(rsx! {
-                Loading {}
+                Spinner { class: "h-[100vh] bg-white dark:bg-gray-900".to_string() }
             });
         },
     }
 }
+
+/// components & renderers shared with templates.
+pub fn shared_utility(config: &Config) -> SharedUtility {
+    let mut renderers: HashMap<String, fn(Scope<karaty_blueprint::RendererProps>) -> Element> =
+        HashMap::new();
+    renderers.insert("markdown".to_string(), Markdown);
+    renderers.insert("html".to_string(), Html);
+
+    SharedUtility {
+        navbar: Navbar,
+        footer: Footer,
+        giscus: GiscusWithConfig,
+        last_updated: LastUpdated,
+        _404: PageNotFound,
+        error: Error,
+        renderers,
+        app_config: config.clone(),
+    }
+}
+
+/// value of `:name` segment in the access path (base path is stripped).
+fn path_segment<'a>(bound: &str, access: &'a str, name: &str) -> Option<&'a str> {
+    let index = bound
+        .trim_start_matches('/')
+        .split('/')
+        .position(|segment| segment.starts_with(':') && &segment[1..] == name)?;
+    access.trim_start_matches('/').split('/').nth(index)
+}
+
+/// segment value, nested segments (`:name-1`, `:name-2` ...) will be joined by `/`.
+fn nested_segment(bound: &str, access: &str, name: &str) -> Option<String> {
+    let mut result = path_segment(bound, access, name)?.to_string();
+    for i in 1..MAX_NESTED_DEPTH {
+        match path_segment(bound, access, &format!("{name}-{i}")) {
+            Some(value) => result.push_str(&format!("/{value}")),
+            None => break,
+        }
+    }
+    Some(result)
+}
+
+/// check if the segment is generated by `nested` route, e.g. `path-1` for `path`.
+fn is_nested_segment(list: &[String], name: &str) -> bool {
+    match name.rsplit_once('-') {
+        Some((base, index)) => index.parse::<usize>().is_ok() && list.iter().any(|v| v == base),
+        None => false,
+    }
+}
+
+/// remove the trailing slash of path, root path keeps `/`.
+fn normalize_path(path: &str) -> &str {
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
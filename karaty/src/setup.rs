@@ -1,7 +1,19 @@
 use dioxus::prelude::*;
 use fermi::use_init_atom_root;
 
-use crate::{config::Config, hooks::mode::init_mode_info, utils::data::GlobalData};
+use std::rc::Rc;
+
+use crate::{
+    config::Config,
+    hooks::{language::init_language, mode::init_mode_info},
+    utils::{
+        analytics,
+        data::GlobalData,
+        head,
+        markdown::{MarkdownRenderer, SharedRenderer},
+        math, mermaid, offline, print,
+    },
+};
 
 pub async fn setup_config() -> anyhow::Result<Config> {
     let window = web_sys::window().unwrap();
@@ -14,18 +26,67 @@ pub async fn setup_config() -> anyhow::Result<Config> {
     let response = gloo::net::http::Request::get(&toml_path).send().await?;
     let content = response.text().await.unwrap_or_default();
     let result = toml::from_str::<Config>(&content)?;
+    result.validate()?;
     Ok(result)
 }
 
 pub fn setup_root_app(cx: &Scope, data: GlobalData) -> anyhow::Result<()> {
     cx.provide_context(data.clone());
+    // templates read `base-path` from it
+    cx.provide_context(data.config.site.clone());
+    cx.provide_context(SharedRenderer(Rc::new(MarkdownRenderer {
+        math: data.config.site.math,
+        ..MarkdownRenderer::from_flavor(data.config.site.markdown_flavor.as_deref())
+    })));
 
     let _ = js_sys::eval(&format!(
         "document.title = 'Home{}'",
         data.config.site.title_suffix
     ));
 
+    cx.use_hook(|| {
+        if let Some(favicon) = &data.config.site.favicon {
+            head::set_favicon(&data.config.site.with_base(favicon));
+        }
+        // appended to the end of head, so custom rules override the default styles
+        if let Some(href) = &data.config.site.custom_css {
+            head::add_stylesheet(&data.config.site.with_base(href), &[]);
+        }
+        // anchor targets (headings, footnotes ...) must not be hidden under the fixed navbar
+        let anchor_offset = data.config.navigation.anchor_offset();
+        if anchor_offset > 0 {
+            head::add_style(
+                "karaty-anchor-offset",
+                &format!("[id] {{ scroll-margin-top: {anchor_offset}px; }}"),
+            );
+        }
+        if data.config.site.print_style {
+            print::load();
+        }
+        if let Some(css) = &data.config.site.custom_style {
+            head::add_style("karaty-custom-style", css);
+        }
+        if let Some(config) = &data.config.analytics {
+            analytics::inject(config);
+        }
+        if data.config.site.math {
+            math::load();
+        }
+        if data.config.site.mermaid {
+            let site = &data.config.site;
+            let script = site.mermaid_script.as_ref().map(|v| site.with_base(v));
+            mermaid::load(script.as_deref(), site.mermaid_integrity.as_deref());
+        }
+        let base_path = data.config.site.base_path();
+        if data.config.site.offline {
+            offline::register(&base_path);
+        } else {
+            offline::unregister(&base_path);
+        }
+    });
+
     use_init_atom_root(&cx);
+    init_language(cx, &data.config);
     if data.config.site.dark_mode {
         init_mode_info(&cx);
     }
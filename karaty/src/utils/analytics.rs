@@ -0,0 +1,59 @@
+// analytics integration, nothing is loaded without `[analytics]` config.
+
+use crate::config::AnalyticsConfig;
+
+use super::head;
+
+/// inject the analytics script into document head.
+pub fn inject(config: &AnalyticsConfig) {
+    let attributes = config
+        .attributes
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    head::add_script(&config.script, &attributes);
+}
+
+/// browser "Do Not Track" setting is enabled.
+pub fn do_not_track() -> bool {
+    web_sys::window()
+        .map(|v| v.navigator().do_not_track() == "1")
+        .unwrap_or(false)
+}
+
+/// record a click on a link which leaves the site, the request is sent in background.
+pub fn track_outbound(config: &AnalyticsConfig, url: &str) {
+    let track = match config.provider.as_deref() {
+        Some("plausible") => {
+            "window.plausible && window.plausible('Outbound Link: Click', { props: { url: url } });"
+        }
+        Some("umami") => "window.umami && window.umami.track('outbound-link-click', { url: url });",
+        _ => return,
+    };
+    let _ = js_sys::eval(&format!(
+        "(function(url) {{ {track} }})({});",
+        serde_json::to_string(url).unwrap_or_default()
+    ));
+}
+
+/// record a pageview of current url, it will wait for the script when it's still loading.
+pub fn track_pageview(config: &AnalyticsConfig) {
+    let track = match config.provider.as_deref() {
+        Some("plausible") => "window.plausible && window.plausible('pageview');",
+        Some("umami") => "window.umami && window.umami.track();",
+        _ => return,
+    };
+    let ready = match config.provider.as_deref() {
+        Some("plausible") => "window.plausible",
+        _ => "window.umami",
+    };
+    let _ = js_sys::eval(&format!(
+        "(function(src) {{
+            var track = function() {{ {track} }};
+            if ({ready}) {{ track(); return; }}
+            var el = document.head.querySelector('script[src=\"' + src + '\"]');
+            if (el != null) {{ el.addEventListener('load', track); }}
+        }})({});",
+        serde_json::to_string(&config.script).unwrap_or_default()
+    ));
+}
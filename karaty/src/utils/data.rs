@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use anyhow::anyhow;
 use async_recursion::async_recursion;
+use futures_util::{
+    future::{select, Either},
+    stream, StreamExt,
+};
 use karaty_blueprint::{TemplateData, Templates};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::config::{Config, RoutingInfo, TemplateConfig};
+use crate::config::{Config, RoutingInfo, TemplateConfig, DEFAULT_BRANCHES};
+
+use super::search::{flatten_pages, SearchIndex};
 
 #[derive(Debug, Clone)]
 pub struct GlobalData {
@@ -13,22 +18,293 @@ pub struct GlobalData {
     pub routing: Vec<RoutingInfo>,
     pub template_config: TemplateConfig,
     pub templates: HashMap<String, Templates>,
+    /// all page contents (`file path -> content`), filled by `load_search_index` when searching,
+    /// page bodies are otherwise fetched on demand when visited.
+    pub pages: Rc<RefCell<HashMap<String, String>>>,
+    pub search_index: Rc<RefCell<Option<SearchIndex>>>,
+}
+
+impl GlobalData {
+    /// build search index from loaded pages, markdown syntax will be stripped.
+    pub fn build_search_index(&self) -> SearchIndex {
+        SearchIndex::build(&self.pages.borrow(), &self.routing)
+    }
+
+    /// load all pages from data source and build search index, only build once.
+    pub async fn load_search_index(&self) {
+        if self.search_index.borrow().is_some() {
+            return;
+        }
+        let data = load_all_data(&self.config).await;
+        let mut pages = HashMap::new();
+        flatten_pages(&data, "", &mut pages);
+        *self.pages.borrow_mut() = pages;
+        let index = self.build_search_index();
+        *self.search_index.borrow_mut() = Some(index);
+    }
+
+    /// drop loaded pages and search index, so `load_search_index` will run again.
+    pub fn reset_search_index(&self) {
+        self.pages.borrow_mut().clear();
+        *self.search_index.borrow_mut() = None;
+    }
 }
 
-pub fn get_raw_data_url(service: &str, name: &str, branch: &str) -> Option<String> {
+pub fn get_raw_data_url(
+    service: &str,
+    host: Option<&str>,
+    name: &str,
+    branch: &str,
+) -> Option<String> {
     match service.to_lowercase().as_str() {
-        "github" => {
-            Some(format!(
+        "github" => match host {
+            // github enterprise server
+            Some(host) => Some(format!("https://{}/{}/raw/{}", host, name, branch)),
+            None => Some(format!(
                 "https://raw.githubusercontent.com/{}/{}",
                 name, branch,
-            ))
+            )),
         },
         "gitee" => Some(format!("https://gitee.com/{}/raw/{}", name, branch)),
+        "gitlab" => Some(format!(
+            "https://{}/{}/-/raw/{}",
+            host.unwrap_or("gitlab.com"),
+            name,
+            branch
+        )),
+        _ => None,
+    }
+}
+
+/// `api_base` can override the api endpoint, e.g. `https://git.example.com/api/v3`.
+pub fn get_content_list_url(
+    service: &str,
+    host: Option<&str>,
+    api_base: Option<&str>,
+    name: &str,
+    path: &str,
+    branch: &str,
+) -> Option<String> {
+    let api_base = api_base.map(|v| v.trim_end_matches('/').to_string());
+    match service.to_lowercase().as_str() {
+        "github" => Some(format!(
+            "{}/repos/{}/contents/{}?ref={}",
+            api_base.unwrap_or(match host {
+                Some(host) => format!("https://{}/api/v3", host),
+                None => "https://api.github.com".to_string(),
+            }),
+            name,
+            path,
+            branch
+        )),
+        "gitee" => Some(format!(
+            "{}/repos/{}/contents/{}?ref={}",
+            api_base.unwrap_or("https://gitee.com/api/v5".to_string()),
+            name,
+            path.trim_start_matches("./"),
+            branch
+        )),
+        "gitlab" => Some(format!(
+            "{}/projects/{}/repository/tree?path={}&ref={}&per_page=100",
+            api_base.unwrap_or(format!("https://{}/api/v4", host.unwrap_or("gitlab.com"))),
+            name.replace('/', "%2F"),
+            path.trim_start_matches("./"),
+            branch
+        )),
         _ => None,
     }
 }
 
-pub async fn load_from_source(config: &Config, sub_path: &str) -> anyhow::Result<String> {
+/// build a GET request, attach `Authorization` header when token is provided,
+/// and `If-None-Match` header when etag is provided.
+fn request(url: &str, token: Option<&str>, etag: Option<&str>) -> gloo::net::http::RequestBuilder {
+    let mut request = gloo::net::http::Request::get(url);
+    if let Some(token) = token {
+        request = request.header("Authorization", &format!("Bearer {}", token));
+    }
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+    request
+}
+
+/// send a GET request, it will be aborted when no response in `timeout` seconds (`0` disables it).
+async fn send_with_timeout(
+    url: &str,
+    token: Option<&str>,
+    etag: Option<&str>,
+    timeout: u32,
+) -> Result<gloo::net::http::Response, DataError> {
+    if timeout == 0 {
+        return Ok(request(url, token, etag).send().await?);
+    }
+    let controller = web_sys::AbortController::new().ok();
+    let signal = controller.as_ref().map(|v| v.signal());
+    let send = request(url, token, etag)
+        .abort_signal(signal.as_ref())
+        .send();
+    let timer = gloo::timers::future::TimeoutFuture::new(timeout.saturating_mul(1000));
+    futures_util::pin_mut!(send);
+    match select(send, timer).await {
+        Either::Left((result, _)) => Ok(result?),
+        Either::Right(_) => {
+            if let Some(controller) = controller {
+                controller.abort();
+            }
+            Err(DataError::Timeout {
+                path: url.to_string(),
+                seconds: timeout,
+            })
+        }
+    }
+}
+
+/// send a GET request, retry with exponential backoff on network error & server error (5xx),
+/// timed out request is not retried.
+async fn send_request(
+    config: &Config,
+    url: &str,
+    token: Option<&str>,
+    etag: Option<&str>,
+) -> Result<gloo::net::http::Response, DataError> {
+    let retry_count = config.data_source.retry_count;
+    let retry_delay = config.data_source.retry_delay;
+    let timeout = config.data_source.timeout;
+    let mut attempt = 0;
+    loop {
+        let result = send_with_timeout(url, token, etag, timeout).await;
+        let retryable = match &result {
+            Ok(response) => response.status() >= 500,
+            Err(DataError::Timeout { .. }) => false,
+            Err(_) => true,
+        };
+        if !retryable || attempt >= retry_count {
+            return result;
+        }
+        let delay = retry_delay.saturating_mul(2u32.saturating_pow(attempt));
+        gloo::timers::future::TimeoutFuture::new(delay).await;
+        attempt += 1;
+    }
+}
+
+/// data load error, `NotFound` means the content truly not exists (HTTP 404).
+#[derive(Debug)]
+pub enum DataError {
+    NotFound(String),
+    Status { path: String, status: u16 },
+    /// api rate limit exceeded, `reset` is the unix timestamp (seconds) when limit resets
+    RateLimited { reset: Option<u64> },
+    /// request failed before any response (offline, dns, cors ...)
+    Network(String),
+    /// response can't be parsed (e.g. invalid directory listing json)
+    Parse(String),
+    /// invalid `data-source` configuration
+    Config(String),
+    /// no response in `seconds`, the request has been aborted
+    Timeout { path: String, seconds: u32 },
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::NotFound(path) => write!(f, "Content `{}` not found", path),
+            DataError::Status { path, status } => {
+                write!(f, "Request `{}` failed with status {}", path, status)
+            }
+            DataError::RateLimited { reset } => {
+                write!(f, "Rate limited by data source, try again later")?;
+                if let Some(reset) = reset {
+                    let date = js_sys::Date::new(&((*reset as f64) * 1000.0).into());
+                    let time = String::from(date.to_locale_time_string("default"));
+                    write!(f, " (resets at {})", time)?;
+                }
+                Ok(())
+            }
+            DataError::Network(err) => write!(f, "Network error: {}", err),
+            DataError::Parse(err) => write!(f, "Invalid content: {}", err),
+            DataError::Config(err) => write!(f, "Invalid data source config: {}", err),
+            DataError::Timeout { path, seconds } => {
+                write!(f, "Content `{}` timed out after {} seconds", path, seconds)
+            }
+            DataError::Other(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+impl From<anyhow::Error> for DataError {
+    fn from(value: anyhow::Error) -> Self {
+        // keep the original error if it was converted from `DataError`
+        match value.downcast::<DataError>() {
+            Ok(err) => err,
+            Err(err) => DataError::Other(err),
+        }
+    }
+}
+
+impl From<gloo::net::Error> for DataError {
+    fn from(value: gloo::net::Error) -> Self {
+        match value {
+            gloo::net::Error::SerdeError(err) => DataError::Parse(err.to_string()),
+            err => DataError::Network(err.to_string()),
+        }
+    }
+}
+
+thread_local! {
+    /// fetched content cache, keep data for the lifetime of the app.
+    static CONTENT_CACHE: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// clear all cached content, next load will fetch from data source again.
+#[allow(dead_code)]
+pub fn clear_cache() {
+    CONTENT_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// load content from data source, `refresh` will bypass the content cache.
+/// translation of `lang` is preferred, the original file is used when it's missing.
+pub async fn load_from_source(
+    config: &Config,
+    sub_path: &str,
+    lang: Option<&str>,
+    refresh: bool,
+) -> Result<String, DataError> {
+    let localized = config
+        .i18n
+        .as_ref()
+        .zip(lang)
+        .and_then(|(i18n, lang)| i18n.localized_path(sub_path, lang));
+    if let Some(localized) = localized {
+        match load_content(config, &localized, refresh).await {
+            Err(DataError::NotFound(_)) => {}
+            result => return result,
+        }
+    }
+    load_content(config, sub_path, refresh).await
+}
+
+async fn load_content(config: &Config, sub_path: &str, refresh: bool) -> Result<String, DataError> {
+    if !refresh {
+        let cached = CONTENT_CACHE.with(|cache| cache.borrow().get(sub_path).cloned());
+        if let Some(content) = cached {
+            return Ok(content);
+        }
+    }
+    let content = fetch_from_source(config, sub_path).await?;
+    CONTENT_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert(sub_path.to_string(), content.clone())
+    });
+    Ok(content)
+}
+
+/// get the data sources in use (primary source & mirrors in order),
+/// local data source will be used for local access.
+fn data_sources(config: &Config) -> Vec<(String, toml::Value)> {
     let window = web_sys::window().unwrap();
     let host = window.location().host().unwrap();
     let host = host
@@ -38,193 +314,866 @@ pub async fn load_from_source(config: &Config, sub_path: &str) -> anyhow::Result
         .unwrap()
         .to_string();
 
-    let mut source_mode = config.data_source.mode.clone();
-    let mut source_data = config.data_source.data.clone();
     if let Some(local) = config.data_source.local.clone() {
         if host.as_str() == "localhost"
             || host.as_str() == "127.0.0.1"
             || host.starts_with("192.168")
         {
-            source_mode = local.mode;
-            source_data = local.data;
+            return vec![(local.mode, local.data)];
         }
     }
+    let mut sources = vec![(
+        config.data_source.mode.clone(),
+        config.data_source.data.clone(),
+    )];
+    for mirror in &config.data_source.mirrors {
+        sources.push((mirror.mode.clone(), mirror.data.clone()));
+    }
+    sources
+}
+
+/// custom url & local source never receive the repository token
+fn source_token<'a>(config: &'a Config, source_mode: &str) -> Option<&'a str> {
+    match source_mode.to_lowercase().as_str() {
+        "custom-url" | "local" => None,
+        _ => config.data_source.token.as_deref(),
+    }
+}
+
+thread_local! {
+    /// default branch found by probing: repository -> branch
+    static PROBED_BRANCH: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
 
+/// repository of the source and its configured branch, `None` for custom url & local source.
+fn source_repository(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+) -> Option<(String, Option<String>)> {
     match source_mode.to_lowercase().as_str() {
         "independent-repository" => {
-            let source = source_data.as_table().unwrap();
+            let service = source_data.get("service")?.as_str()?;
+            let name = source_data.get("name")?.as_str()?;
+            let branch = source_data.get("branch").and_then(|v| v.as_str());
+            Some((format!("{}:{}", service, name), branch.map(String::from)))
+        }
+        "embedded-repository" => {
+            let repository = &config.repository;
+            Some((
+                format!("{}:{}", repository.service, repository.name),
+                repository.branch.clone(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// branches to try in order: configured branch, probed branch, or `main` then `master`.
+fn branch_candidates(config: &Config, source_mode: &str, source_data: &toml::Value) -> Vec<String> {
+    match source_repository(config, source_mode, source_data) {
+        Some((_, Some(branch))) => vec![branch],
+        Some((repository, None)) => {
+            let probed = PROBED_BRANCH.with(|v| v.borrow().get(&repository).cloned());
+            match probed {
+                Some(branch) => vec![branch],
+                None => DEFAULT_BRANCHES.iter().map(|v| v.to_string()).collect(),
+            }
+        }
+        None => vec![String::new()],
+    }
+}
+
+/// remember the branch which content is found in, later requests will use it directly.
+fn remember_branch(config: &Config, source_mode: &str, source_data: &toml::Value, branch: &str) {
+    if let Some((repository, None)) = source_repository(config, source_mode, source_data) {
+        PROBED_BRANCH.with(|v| v.borrow_mut().insert(repository, branch.to_string()));
+    }
+}
+
+/// missing content won't fall back to mirror, network error & 5xx will.
+fn should_fallback(err: &DataError) -> bool {
+    match err {
+        DataError::NotFound(_) => false,
+        DataError::Status { status, .. } => *status >= 500,
+        DataError::RateLimited { .. }
+        | DataError::Network(_)
+        | DataError::Parse(_)
+        | DataError::Config(_)
+        | DataError::Timeout { .. }
+        | DataError::Other(_) => true,
+    }
+}
+
+fn source_table<'a>(
+    data: &'a toml::Value,
+    mode: &str,
+) -> Result<&'a toml::map::Map<String, toml::Value>, DataError> {
+    data.as_table().ok_or_else(|| {
+        DataError::Config(format!(
+            "data_source.data must be a table in `{}` mode",
+            mode
+        ))
+    })
+}
+
+fn source_str<'a>(
+    source: &'a toml::map::Map<String, toml::Value>,
+    key: &str,
+) -> Result<&'a str, DataError> {
+    source.get(key).and_then(|v| v.as_str()).ok_or_else(|| {
+        DataError::Config(format!(
+            "data_source.data.{} is missing or not a string",
+            key
+        ))
+    })
+}
+
+fn unsupported_service(service: &str) -> DataError {
+    DataError::Config(format!("Unsupported git service `{}`", service))
+}
+
+/// check `403` / `429` response with `X-RateLimit-Remaining: 0` header.
+fn rate_limit_error(response: &gloo::net::http::Response) -> Option<DataError> {
+    if response.status() != 403 && response.status() != 429 {
+        return None;
+    }
+    let headers = response.headers();
+    if headers.get("x-ratelimit-remaining").as_deref() != Some("0") {
+        return None;
+    }
+    let reset = headers
+        .get("x-ratelimit-reset")
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    Some(DataError::RateLimited { reset })
+}
+
+/// read response text, 404 status will be reported as `DataError::NotFound`.
+async fn response_text(
+    response: gloo::net::http::Response,
+    sub_path: &str,
+) -> Result<String, DataError> {
+    if let Some(err) = rate_limit_error(&response) {
+        return Err(err);
+    }
+    if response.status() == 404 {
+        return Err(DataError::NotFound(sub_path.to_string()));
+    }
+    if !response.ok() {
+        return Err(DataError::Status {
+            path: sub_path.to_string(),
+            status: response.status(),
+        });
+    }
+    Ok(response.text().await?)
+}
 
-            let service = source.get("service").unwrap().as_str().unwrap();
-            let name = source.get("name").unwrap().as_str().unwrap();
-            let branch = source.get("branch").unwrap().as_str().unwrap();
+/// path of `local` data source, it is served by the same server with the app (e.g. `/data`),
+/// `site.base-path` is prepended.
+fn local_base_path(config: &Config, data: &toml::Value) -> Result<String, DataError> {
+    let path = data.as_str().ok_or_else(|| {
+        DataError::Config("data_source.data must be a string in `local` mode".to_string())
+    })?;
+    Ok(config.site.with_base(&format!("/{}", path.trim_matches('/'))))
+}
 
-            let raw_url = get_raw_data_url(service, name, branch).expect("service not found");
+/// base url of the data source, file path in data source can be appended to it.
+pub fn source_base_url(config: &Config) -> Result<String, DataError> {
+    let (source_mode, source_data) = data_sources(config).remove(0);
+    let branch = branch_candidates(config, &source_mode, &source_data).remove(0);
+    let base_url = base_url_of(config, &source_mode, &source_data, &branch)?;
+    let content_dir = config.data_source.content_path("");
+    if content_dir.is_empty() {
+        return Ok(base_url);
+    }
+    Ok(format!("{}/{}", base_url, content_dir))
+}
 
-            let response = gloo::net::http::Request::get(&format!("{}/{}", raw_url, sub_path))
-                .send()
-                .await?;
+/// `branch` is used by repository modes, see [`branch_candidates`].
+fn base_url_of(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    branch: &str,
+) -> Result<String, DataError> {
+    match source_mode.to_lowercase().as_str() {
+        "independent-repository" => {
+            let source = source_table(source_data, "independent-repository")?;
 
-            return Ok(response.text().await?);
+            let service = source_str(source, "service")?;
+            let host = source.get("host").and_then(|v| v.as_str());
+            let name = source_str(source, "name")?;
+
+            get_raw_data_url(service, host, name, branch)
+                .ok_or_else(|| unsupported_service(service))
         }
         "embedded-repository" => {
             let source = config.repository.clone();
             let service = source.service;
             let name = source.name;
-            let branch = source.branch;
 
-            let sub_folder = source_data.as_str().unwrap();
+            let sub_folder = source_data.as_str().ok_or_else(|| {
+                DataError::Config(
+                    "data_source.data must be a string in `embedded-repository` mode".to_string(),
+                )
+            })?;
 
-            let raw_url = get_raw_data_url(&service, &name, &branch).expect("service not found");
+            let raw_url = get_raw_data_url(&service, source.host.as_deref(), &name, branch)
+                .ok_or_else(|| unsupported_service(&service))?;
 
-            let response =
-                gloo::net::http::Request::get(&format!("{}/{}/{}", raw_url, sub_folder, sub_path))
-                    .send()
-                    .await?;
-            return Ok(response.text().await?);
+            Ok(format!("{}/{}", raw_url, sub_folder))
         }
         "custom-url" => {
-            let source = source_data.as_table().unwrap();
-            let url = source.get("url").unwrap().as_str().unwrap();
-            let url = format!("{}/{}", url, sub_path);
-            let response = gloo::net::http::Request::get(&url).send().await?;
-            return Ok(response.text().await?);
+            let source = source_table(source_data, "custom-url")?;
+            Ok(source_str(source, "url")?.to_string())
         }
-        _ => {}
+        "local" => Ok(local_base_path(config, source_data)?),
+        _ => Err(DataError::Config(format!(
+            "Unknown load mode `{}`",
+            source_mode
+        ))),
     }
-    return Err(anyhow!("Unknown load mode"));
 }
 
-pub async fn load_content_list(config: &Config, sub_path: &str) -> Vec<(String, String)> {
-    let mut result = Vec::new();
+/// fetch content from data sources in order, return the first success.
+async fn fetch_from_source(config: &Config, sub_path: &str) -> Result<String, DataError> {
+    let sub_path = &config.data_source.content_path(sub_path);
+    let mut last_error = None;
+    for (source_mode, source_data) in data_sources(config) {
+        let result = fetch_from(config, &source_mode, &source_data, sub_path).await;
+        match result {
+            Err(err) if should_fallback(&err) => {
+                log::warn!("load `{}` from `{}` failed: {}", sub_path, source_mode, err);
+                last_error = Some(err);
+            }
+            result => return result,
+        }
+    }
+    Err(last_error.unwrap_or(DataError::Config("No data source available".to_string())))
+}
 
-    let window = web_sys::window().unwrap();
-    let host = window.location().host().unwrap();
-    let host = host
-        .split(":")
-        .collect::<Vec<&str>>()
-        .first()
-        .unwrap()
-        .to_string();
+/// fetch content from one data source, default branches will be probed in order
+/// when the repository branch is not configured.
+async fn fetch_from(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    sub_path: &str,
+) -> Result<String, DataError> {
+    let candidates = branch_candidates(config, source_mode, source_data);
+    let last = candidates.len() - 1;
+    for (index, branch) in candidates.iter().enumerate() {
+        let result = fetch_from_branch(config, source_mode, source_data, sub_path, branch).await;
+        match result {
+            Err(DataError::NotFound(_)) if index < last => continue,
+            Ok(_) if last > 0 => remember_branch(config, source_mode, source_data, branch),
+            _ => {}
+        }
+        return result;
+    }
+    Err(DataError::Config("No branch available".to_string()))
+}
 
-    let mut source_mode = config.data_source.mode.clone();
-    let mut source_data = config.data_source.data.clone();
-    if let Some(local) = config.data_source.local.clone() {
-        if host.as_str() == "localhost"
-            || host.as_str() == "127.0.0.1"
-            || host.starts_with("192.168")
-        {
-            source_mode = local.mode;
-            source_data = local.data;
+async fn fetch_from_branch(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    sub_path: &str,
+    branch: &str,
+) -> Result<String, DataError> {
+    let token = source_token(config, source_mode);
+    let base_url = base_url_of(config, source_mode, source_data, branch)?;
+    let url = format!("{}/{}", base_url, sub_path);
+    // requests with token already need a cors preflight (`Authorization` header), so they are
+    // sent conditionally with the stored etag. without token, a conditional header would add a
+    // preflight to every request, the browser http cache is used instead.
+    let stored = token.and_then(|_| stored_response(&url));
+    let etag = stored.as_ref().map(|v| v.etag.as_str());
+    let response = match (send_request(config, &url, token, etag).await, stored) {
+        // offline, serve the stored response like the service worker does
+        (Err(DataError::Network(err)), Some(stored)) => {
+            log::warn!("`{}` request failed, served from cache: {}", sub_path, err);
+            return Ok(stored.body);
+        }
+        (Ok(response), Some(stored)) if response.status() == 304 => {
+            log::debug!("`{}` not modified, served from cache", sub_path);
+            return Ok(stored.body);
         }
+        (result, _) => result?,
+    };
+    let etag = response
+        .headers()
+        .get("etag")
+        .filter(|_| token.is_some() && response.ok());
+    let content = response_text(response, sub_path).await?;
+    if let Some(etag) = etag {
+        store_response(&url, &etag, &content, None);
+    }
+    Ok(content)
+}
+
+/// local storage key prefix of responses with etag.
+const ETAG_STORAGE_PREFIX: &str = "karaty-etag:";
+/// local storage key of stored response urls, from the least recently stored.
+const ETAG_INDEX_KEY: &str = "karaty-etag-index";
+/// max count of stored responses, the least recently stored ones will be removed.
+const MAX_STORED_RESPONSES: usize = 50;
+/// larger response body (in bytes) will not be stored.
+const MAX_STORED_BODY: usize = 256 * 1024;
+
+/// response stored for conditional request, keep it across page reloads.
+#[derive(Serialize, Deserialize)]
+struct StoredResponse {
+    etag: String,
+    body: String,
+    /// `Link` header, the next page of directory list
+    #[serde(default)]
+    link: Option<String>,
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn stored_response(url: &str) -> Option<StoredResponse> {
+    let value = local_storage()?
+        .get_item(&format!("{ETAG_STORAGE_PREFIX}{url}"))
+        .ok()??;
+    serde_json::from_str(&value).ok()
+}
+
+/// move `url` to the end of stored list, return the urls exceed `max` (removed from list).
+fn push_recent(index: &mut Vec<String>, url: &str, max: usize) -> Vec<String> {
+    index.retain(|v| v != url);
+    index.push(url.to_string());
+    let overflow = index.len().saturating_sub(max);
+    index.drain(..overflow).collect()
+}
+
+/// store response body with etag, at most [`MAX_STORED_RESPONSES`] responses are kept,
+/// the oldest ones will be removed when storage quota exceeded.
+fn store_response(url: &str, etag: &str, body: &str, link: Option<&str>) {
+    if body.len() > MAX_STORED_BODY {
+        return;
+    }
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let value = StoredResponse {
+        etag: etag.to_string(),
+        body: body.to_string(),
+        link: link.map(|v| v.to_string()),
+    };
+    let Ok(value) = serde_json::to_string(&value) else {
+        return;
+    };
+    let mut index = storage
+        .get_item(ETAG_INDEX_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
+        .unwrap_or_default();
+    for removed in push_recent(&mut index, url, MAX_STORED_RESPONSES) {
+        let _ = storage.remove_item(&format!("{ETAG_STORAGE_PREFIX}{removed}"));
     }
+    while let Err(err) = storage.set_item(&format!("{ETAG_STORAGE_PREFIX}{url}"), &value) {
+        // current url is the last one, nothing else can be removed
+        if index.len() <= 1 {
+            log::warn!("store response of `{}` failed: {:?}", url, err);
+            index.clear();
+            break;
+        }
+        let oldest = index.remove(0);
+        let _ = storage.remove_item(&format!("{ETAG_STORAGE_PREFIX}{oldest}"));
+    }
+    if let Ok(index) = serde_json::to_string(&index) {
+        let _ = storage.set_item(ETAG_INDEX_KEY, &index);
+    }
+}
+
+/// max page count of one directory content list.
+const MAX_CONTENT_PAGES: usize = 100;
+
+/// find `rel="next"` url in `Link` header.
+fn next_page_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|param| param.trim().replace(' ', "") == "rel=\"next\"");
+        if is_next {
+            Some(url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+        } else {
+            None
+        }
+    })
+}
 
-    let target = match source_mode.to_lowercase().as_str() {
+/// load directory content list from data sources in order, return the first success.
+pub async fn load_content_list(
+    config: &Config,
+    sub_path: &str,
+) -> Result<Vec<(String, String)>, DataError> {
+    let sub_path = &config.data_source.content_path(sub_path);
+    let mut last_error = None;
+    for (source_mode, source_data) in data_sources(config) {
+        let result = content_list_from(config, &source_mode, &source_data, sub_path).await;
+        match result {
+            Err(err) if should_fallback(&err) => {
+                log::warn!("list `{}` from `{}` failed: {}", sub_path, source_mode, err);
+                last_error = Some(err);
+            }
+            result => return result,
+        }
+    }
+    Err(last_error.unwrap_or(DataError::Config("No data source available".to_string())))
+}
+
+fn content_list_url(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    sub_path: &str,
+    branch: &str,
+) -> Result<String, DataError> {
+    match source_mode.to_lowercase().as_str() {
         "independent-repository" => {
-            let source = source_data.as_table().unwrap();
+            let source = source_table(source_data, "independent-repository")?;
 
-            let name = source.get("name").unwrap().as_str().unwrap().to_string();
-            let branch = source.get("branch").unwrap().as_str().unwrap().to_string();
+            let service = source_str(source, "service")?;
+            let host = source.get("host").and_then(|v| v.as_str());
+            let name = source_str(source, "name")?;
 
-            format!(
-                "https://api.github.com/repos/{}/contents/{}?ref={}",
-                name, sub_path, branch
-            )
+            let api_base = source.get("api-base").and_then(|v| v.as_str());
+
+            get_content_list_url(service, host, api_base, name, sub_path, branch)
+                .ok_or_else(|| unsupported_service(service))
         }
         "embedded-repository" => {
             let source = config.repository.clone();
             let name = source.name;
-            let branch = source.branch;
 
-            let sub_folder = source_data.as_str().unwrap();
+            let sub_folder = source_data.as_str().ok_or_else(|| {
+                DataError::Config(
+                    "data_source.data must be a string in `embedded-repository` mode".to_string(),
+                )
+            })?;
 
-            format!(
-                "https://api.github.com/repos/{}/contents/{}/{}?ref={}",
-                name, sub_folder, sub_path, branch,
+            get_content_list_url(
+                &source.service,
+                source.host.as_deref(),
+                source.api_base.as_deref(),
+                &name,
+                &format!("{}/{}", sub_folder, sub_path),
+                branch,
             )
+            .ok_or_else(|| unsupported_service(&source.service))
         }
         "custom-url" => {
-            let source = source_data.as_table().unwrap();
-            let url = source.get("url").unwrap().as_str().unwrap();
-            let index = source.get("index-file").unwrap().as_str().unwrap();
-            format!("{}/{}/{}", url, sub_path, index)
+            let source = source_table(source_data, "custom-url")?;
+            let url = source_str(source, "url")?;
+            let index = source_str(source, "index-file")?;
+            Ok(format!("{}/{}/{}", url, sub_path, index))
         }
-        _ => {
-            panic!("source mode not found");
+        "local" => {
+            // index file is generated by build script for every directory in `public`
+            Ok(format!(
+                "{}/{}/_index.json",
+                local_base_path(config, source_data)?,
+                sub_path
+            ))
         }
-    };
+        _ => Err(DataError::Config(format!(
+            "Unknown load mode `{}`",
+            source_mode
+        ))),
+    }
+}
+
+/// list directory from one data source, default branches will be probed like [`fetch_from`].
+async fn content_list_from(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    sub_path: &str,
+) -> Result<Vec<(String, String)>, DataError> {
+    let candidates = branch_candidates(config, source_mode, source_data);
+    let last = candidates.len() - 1;
+    for (index, branch) in candidates.iter().enumerate() {
+        let result =
+            content_list_from_branch(config, source_mode, source_data, sub_path, branch).await;
+        match result {
+            Err(DataError::NotFound(_)) if index < last => continue,
+            Ok(_) if last > 0 => remember_branch(config, source_mode, source_data, branch),
+            _ => {}
+        }
+        return result;
+    }
+    Err(DataError::Config("No branch available".to_string()))
+}
+
+async fn content_list_from_branch(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    sub_path: &str,
+    branch: &str,
+) -> Result<Vec<(String, String)>, DataError> {
+    let mut result = Vec::new();
+
+    let target = content_list_url(config, source_mode, source_data, sub_path, branch)?;
+    let token = source_token(config, source_mode);
+
+    // large directory will be split into multiple pages, follow `Link: rel="next"`
+    let mut next = Some(target);
+    let mut visited = Vec::new();
+    while let Some(target) = next.take() {
+        if visited.contains(&target) || visited.len() >= MAX_CONTENT_PAGES {
+            break;
+        }
+        visited.push(target.clone());
 
-    let resp = gloo::net::http::Request::get(&target).send().await;
-
-    if let Ok(resp) = resp {
-        let res = resp.json::<Vec<serde_json::Value>>().await;
-        if let Ok(list) = res {
-            for data in list {
-                let file_name = data.get("name").unwrap().as_str().unwrap().to_string();
-                result.push((
-                    data.get("type").unwrap().as_str().unwrap().to_string(),
-                    file_name,
-                ));
+        // api responses are stored with etag, `304 Not Modified` doesn't count against rate limit
+        let stored = stored_response(&target);
+        let etag = stored.as_ref().map(|v| v.etag.as_str());
+        let (body, link) = match (send_request(config, &target, token, etag).await, stored) {
+            // offline, serve the stored response like the service worker does
+            (Err(DataError::Network(err)), Some(stored)) => {
+                log::warn!("`{}` request failed, served from cache: {}", sub_path, err);
+                (stored.body, stored.link)
+            }
+            (Ok(resp), Some(stored)) if resp.status() == 304 => {
+                log::debug!("`{}` not modified, served from cache", sub_path);
+                (stored.body, stored.link)
+            }
+            (Ok(resp), _) => {
+                if let Some(err) = rate_limit_error(&resp) {
+                    return Err(err);
+                }
+                if resp.status() == 404 && visited.len() == 1 {
+                    return Err(DataError::NotFound(sub_path.to_string()));
+                }
+                if resp.status() >= 500 {
+                    return Err(DataError::Status {
+                        path: sub_path.to_string(),
+                        status: resp.status(),
+                    });
+                }
+                let link = resp.headers().get("link");
+                let etag = resp.headers().get("etag").filter(|_| resp.ok());
+                let body = resp.text().await?;
+                if let Some(etag) = etag {
+                    store_response(&target, &etag, &body, link.as_deref());
+                }
+                (body, link)
+            }
+            (Err(err), _) => return Err(err),
+        };
+        next = link.and_then(|link| next_page_link(&link));
+        let list = serde_json::from_str::<Vec<serde_json::Value>>(&body)
+            .map_err(|err| DataError::Parse(err.to_string()))?;
+        for data in list {
+            let file_name = data.get("name").and_then(|v| v.as_str());
+            let file_type = data.get("type").and_then(|v| v.as_str());
+            if let (Some(file_name), Some(file_type)) = (file_name, file_type) {
+                // gitlab use `tree` & `blob` to mark directory and file
+                let file_type = match file_type {
+                    "tree" => "dir",
+                    "blob" => "file",
+                    other => other,
+                };
+                result.push((file_type.to_string(), file_name.to_string()));
             }
         }
     }
 
-    result
+    Ok(result)
 }
 
-#[allow(dead_code)]
 pub async fn load_all_data(config: &Config) -> HashMap<String, TemplateData> {
     let mut result = HashMap::new();
-    let contents = load_content_list(config, "./").await;
-    for (tp, name) in contents {
-        let path = format!("{name}");
-        let content = if tp == "file" {
-            let content = load_from_source(config, &path).await;
-            content.map(|v| TemplateData::File(v))
-        } else {
-            let dirs = load_content_list(config, &path).await;
-            let dirs = dirs
-                .iter()
-                .map(|v| (v.0.clone(), format!("{name}/{}", v.1)))
-                .collect();
-            let dir = load_page_from_dir(config, dirs).await;
-            dir
+    let contents = load_content_list(config, "./").await.unwrap_or_else(|err| {
+        log::error!("load content list failed: {}", err);
+        vec![]
+    });
+    let (files, dirs): (Vec<_>, Vec<_>) =
+        contents.into_iter().partition(|(tp, _)| tp == "file");
+    let files = files.into_iter().map(|(_, name)| (name.clone(), name)).collect();
+    for (name, content) in load_files(config, files).await {
+        if let Ok(content) = content {
+            result.insert(name, TemplateData::File(content));
+        }
+    }
+    for (_, name) in dirs {
+        let content = match load_content_list(config, &name).await {
+            Ok(dirs) => {
+                let dirs = dirs
+                    .iter()
+                    .map(|v| (v.0.clone(), format!("{name}/{}", v.1)))
+                    .collect();
+                load_page_from_dir(config, dirs, 0).await
+            }
+            Err(e) => Err(e.into()),
         };
         if let Ok(content) = content {
-            result.insert(name.to_string(), content);
+            result.insert(name, content);
         }
     }
     result
 }
 
+/// fetch `(name, path)` files concurrently, at most `data-source.concurrency` requests at a time.
+async fn load_files(
+    config: &Config,
+    files: Vec<(String, String)>,
+) -> Vec<(String, Result<String, DataError>)> {
+    let limit = config.data_source.concurrency.max(1) as usize;
+    stream::iter(files)
+        .map(|(name, path)| async move {
+            let content = load_from_source(config, &path, None, false).await;
+            (name, content)
+        })
+        .buffer_unordered(limit)
+        .collect()
+        .await
+}
+
+/// index file names tried in order when the page file is a directory, e.g. `guide/index.md` for `guide.md`.
+pub const INDEX_FILES: [&str; 2] = ["index", "README"];
+
+/// load a page file, index files of the directory with the same name are tried when it's not found.
+/// returns the path of the loaded file & its content.
+pub async fn load_page_file(
+    config: &Config,
+    file_path: &str,
+    lang: Option<&str>,
+) -> Result<(String, String), DataError> {
+    let err = match load_from_source(config, file_path, lang, false).await {
+        Ok(content) => return Ok((file_path.to_string(), content)),
+        Err(err @ DataError::NotFound(_)) => err,
+        Err(err) => return Err(err),
+    };
+    let Some((dir, extension)) = file_path.rsplit_once('.') else {
+        return Err(err);
+    };
+    for name in INDEX_FILES {
+        let index = format!("{}/{}.{}", dir.trim_end_matches('/'), name, extension);
+        match load_from_source(config, &index, lang, false).await {
+            Ok(content) => return Ok((index, content)),
+            Err(DataError::NotFound(_)) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(err)
+}
+
+/// max depth of nested directories and nested route segments.
+pub const MAX_NESTED_DEPTH: usize = 8;
+
+/// load directory content recursively, directories deeper than `MAX_NESTED_DEPTH` will be skipped.
 #[async_recursion(?Send)]
 pub async fn load_page_from_dir(
     config: &Config,
     contents: Vec<(String, String)>,
+    depth: usize,
 ) -> anyhow::Result<TemplateData> {
     let mut result = HashMap::new();
+    let mut files = vec![];
+    let mut dirs = vec![];
     for (tp, url) in contents {
         let part_name = url.split('/').last().unwrap_or("").to_string();
+        if part_name.is_empty() || part_name == "." || part_name == ".." {
+            continue;
+        }
         if tp == "file" {
-            let content = load_from_source(config, &url).await?;
-            result.insert(part_name, TemplateData::File(content));
+            files.push((part_name, url));
         } else {
-            let items = load_content_list(config, &url).await;
-            let items: Vec<(String, String)> = items
-                .iter()
-                .map(|(t, i)| (t.clone(), format!("{url}/{i}")))
-                .collect();
-            let content = load_page_from_dir(config, items).await?;
-            result.insert(part_name, content);
+            dirs.push((part_name, url));
         }
     }
+    for (part_name, content) in load_files(config, files).await {
+        result.insert(part_name, TemplateData::File(content?));
+    }
+    // sub directories are loaded one by one, so requests never exceed the concurrency limit
+    for (part_name, url) in dirs {
+        if depth >= MAX_NESTED_DEPTH {
+            log::warn!("directory `{}` is too deep, skipped.", url);
+            continue;
+        }
+        let items = load_content_list(config, &url).await?;
+        let items: Vec<(String, String)> = items
+            .iter()
+            .map(|(t, i)| (t.clone(), format!("{url}/{i}")))
+            .collect();
+        let content = load_page_from_dir(config, items, depth + 1).await?;
+        result.insert(part_name, content);
+    }
     Ok(TemplateData::Directory(result))
 }
 
+/// add routes for `file-map` paths which are not bound by routing.
+pub fn apply_file_map(
+    mut routing: Vec<RoutingInfo>,
+    file_map: &HashMap<String, String>,
+) -> Vec<RoutingInfo> {
+    let mut paths = file_map.keys().collect::<Vec<&String>>();
+    paths.sort();
+    for path in paths {
+        let bound = routing.iter().any(|info| match info {
+            RoutingInfo::FileBind { path: p, .. } | RoutingInfo::RedirectBind { path: p, .. } => {
+                p.trim_end_matches('/') == path.trim_end_matches('/')
+            }
+        });
+        if !bound {
+            routing.push(RoutingInfo::FileBind {
+                path: path.clone(),
+                file: file_map[path].clone(),
+                template: String::new(),
+                config: None,
+            });
+        }
+    }
+    routing
+}
+
+/// expand routes with `nested = true` config, the last `:segment` of these routes
+/// can match multiple path levels (e.g. `/guide/:path` will match `/guide/advanced/intro`).
+pub fn expand_nested_routing(routing: Vec<RoutingInfo>) -> Vec<RoutingInfo> {
+    let mut result = vec![];
+    for info in routing {
+        if let RoutingInfo::FileBind {
+            path,
+            file,
+            template,
+            config,
+        } = &info
+        {
+            let nested = config
+                .as_ref()
+                .and_then(|v| v.get("nested"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let segment = path
+                .trim_end_matches('/')
+                .split('/')
+                .next_back()
+                .and_then(|v| v.strip_prefix(':'))
+                .map(|v| v.to_string());
+            if let (true, Some(segment)) = (nested, segment) {
+                let mut nested_path = path.trim_end_matches('/').to_string();
+                for i in 1..MAX_NESTED_DEPTH {
+                    nested_path.push_str(&format!("/:{segment}-{i}"));
+                    result.push(RoutingInfo::FileBind {
+                        path: nested_path.clone(),
+                        file: file.clone(),
+                        template: template.clone(),
+                        config: config.clone(),
+                    });
+                }
+            }
+        }
+        result.push(info);
+    }
+    result
+}
+
 #[derive(Deserialize)]
 struct RoutingWrap {
     routing: Vec<RoutingInfo>,
 }
 
+thread_local! {
+    /// latest commit date of files: file path -> date, `None` when it's unavailable.
+    static LAST_UPDATED: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+/// commits api url of a file in the primary repository source, only GitHub is supported.
+fn file_commits_url(
+    config: &Config,
+    source_mode: &str,
+    source_data: &toml::Value,
+    file_path: &str,
+) -> Option<String> {
+    let path = config.data_source.content_path(file_path);
+    let (service, host, api_base, name, path) = match source_mode.to_lowercase().as_str() {
+        "independent-repository" => (
+            source_data.get("service")?.as_str()?.to_string(),
+            source_data.get("host").and_then(|v| v.as_str()).map(String::from),
+            source_data.get("api-base").and_then(|v| v.as_str()).map(String::from),
+            source_data.get("name")?.as_str()?.to_string(),
+            path,
+        ),
+        "embedded-repository" => {
+            let repository = &config.repository;
+            let sub_folder = source_data.as_str()?.trim_matches('/');
+            (
+                repository.service.clone(),
+                repository.host.clone(),
+                repository.api_base.clone(),
+                repository.name.clone(),
+                format!("{}/{}", sub_folder, path),
+            )
+        }
+        _ => return None,
+    };
+    if !service.eq_ignore_ascii_case("github") {
+        return None;
+    }
+    let api_base = match (api_base, host) {
+        (Some(api_base), _) => api_base.trim_end_matches('/').to_string(),
+        (None, Some(host)) => format!("https://{}/api/v3", host),
+        (None, None) => "https://api.github.com".to_string(),
+    };
+    let mut url = format!("{}/repos/{}/commits?path={}&per_page=1", api_base, name, path);
+    if let Some((_, Some(branch))) = source_repository(config, source_mode, source_data) {
+        url.push_str(&format!("&sha={}", branch));
+    }
+    Some(url)
+}
+
+/// date (`YYYY-MM-DD`) of the latest commit which changed the file, the result is cached per path.
+/// `None` for unsupported source or failed request.
+pub async fn load_last_updated(config: &Config, file_path: &str) -> Option<String> {
+    if let Some(date) = LAST_UPDATED.with(|v| v.borrow().get(file_path).cloned()) {
+        return date;
+    }
+    let (source_mode, source_data) = data_sources(config).remove(0);
+    let date = match file_commits_url(config, &source_mode, &source_data, file_path) {
+        Some(url) => {
+            let token = source_token(config, &source_mode);
+            fetch_commit_date(config, &url, token)
+                .await
+                .map_err(|e| log::warn!("load last updated date of `{}` failed: {}", file_path, e))
+                .ok()
+                .flatten()
+        }
+        None => None,
+    };
+    LAST_UPDATED.with(|v| v.borrow_mut().insert(file_path.to_string(), date.clone()));
+    date
+}
+
+async fn fetch_commit_date(
+    config: &Config,
+    url: &str,
+    token: Option<&str>,
+) -> Result<Option<String>, DataError> {
+    let response = send_with_timeout(url, token, None, config.data_source.timeout).await?;
+    if !response.ok() {
+        return Err(DataError::Status {
+            path: url.to_string(),
+            status: response.status(),
+        });
+    }
+    let commits = response.json::<serde_json::Value>().await?;
+    let date = commits
+        .get(0)
+        .and_then(|v| v.pointer("/commit/committer/date"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.chars().take(10).collect());
+    Ok(date)
+}
+
 pub async fn load_routing_file(url: &str) -> anyhow::Result<Vec<RoutingInfo>> {
     let content = gloo::net::http::Request::get(url)
         .send()
@@ -242,3 +1191,144 @@ pub async fn load_template_file(url: &str) -> anyhow::Result<TemplateConfig> {
         .await?;
     Ok(toml::from_str(&content)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [site]
+        name = "Test"
+        title-suffix = " | Test"
+        dark-mode = false
+
+        [repository]
+        service = "GitHub"
+        name = "user/site"
+
+        [data-source]
+        mode = "embedded-repository"
+        data = "data"
+
+        [navigation]
+        content = []
+
+        [footer]
+        content = []
+    "#;
+
+    fn global_data(pages: &[(&str, &str)], routing: &str) -> GlobalData {
+        let routing = toml::from_str::<RoutingWrap>(routing).unwrap().routing;
+        let pages = pages
+            .iter()
+            .map(|(name, content)| (name.to_string(), content.to_string()))
+            .collect();
+        GlobalData {
+            config: toml::from_str(CONFIG).unwrap(),
+            routing,
+            template_config: Default::default(),
+            templates: HashMap::new(),
+            pages: Rc::new(RefCell::new(pages)),
+            search_index: Default::default(),
+        }
+    }
+
+    #[test]
+    fn search_index_from_pages() {
+        let global = global_data(
+            &[
+                ("pages/about.md", "# About Me\n\nI write **Rust** code."),
+                ("blog/hello.md", "---\ntitle: Hello World\n---\nFirst `post` here."),
+                ("blog/_index.md", "hidden rust"),
+                ("pages/unbound.md", "rust everywhere"),
+            ],
+            r#"
+                [[routing]]
+                path = "/about"
+                file = "pages/about.md"
+
+                [[routing]]
+                path = "/blog/:name"
+                file = "blog/{name}.md"
+            "#,
+        );
+        let index = global.build_search_index();
+
+        let names = index.pages.iter().map(|v| v.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["blog/hello.md", "pages/about.md"]);
+        assert_eq!(index.pages[0].title, "Hello World");
+        assert_eq!(index.pages[0].link, "/blog/hello");
+        assert_eq!(index.pages[1].title, "About Me");
+
+        // markdown syntax is stripped, tokens come from visible text only
+        assert_eq!(index.tokens.get("rust"), Some(&vec!["pages/about.md".to_string()]));
+        assert!(!index.tokens.contains_key("title"));
+        assert_eq!(index.lookup("wor"), ["blog/hello.md"]);
+        assert_eq!(
+            index.excerpts.get("blog/hello.md").map(|v| v.as_str()),
+            Some("First post here.")
+        );
+    }
+
+    #[test]
+    fn stored_responses_are_bounded() {
+        let mut index = vec![];
+        assert!(push_recent(&mut index, "a", 2).is_empty());
+        assert!(push_recent(&mut index, "b", 2).is_empty());
+        // stored again, `a` becomes the most recent one
+        assert!(push_recent(&mut index, "a", 2).is_empty());
+        assert_eq!(push_recent(&mut index, "c", 2), vec!["b".to_string()]);
+        assert_eq!(index, vec!["a".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn data_error_conversions() {
+        let err = DataError::from(anyhow::Error::new(DataError::NotFound("a.md".to_string())));
+        assert!(matches!(err, DataError::NotFound(path) if path == "a.md"));
+        let err = DataError::from(anyhow::anyhow!("unknown"));
+        assert!(matches!(err, DataError::Other(_)));
+
+        let serde = serde_json::from_str::<Vec<String>>("{").unwrap_err();
+        let err = DataError::from(gloo::net::Error::SerdeError(serde));
+        assert!(matches!(err, DataError::Parse(_)));
+        let err = DataError::from(gloo::net::Error::GlooError("offline".to_string()));
+        assert!(matches!(err, DataError::Network(msg) if msg.contains("offline")));
+    }
+
+    #[test]
+    fn data_error_fallback() {
+        assert!(!should_fallback(&DataError::NotFound("a.md".to_string())));
+        let status = |status| DataError::Status {
+            path: "a.md".to_string(),
+            status,
+        };
+        assert!(!should_fallback(&status(403)));
+        assert!(should_fallback(&status(502)));
+        assert!(should_fallback(&DataError::RateLimited { reset: None }));
+        assert!(should_fallback(&DataError::Network("offline".to_string())));
+        assert!(should_fallback(&DataError::Timeout {
+            path: "a.md".to_string(),
+            seconds: 10,
+        }));
+    }
+
+    #[test]
+    fn invalid_source_is_config_error() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let source = toml::Value::Table(toml::from_str("service = \"github\"").unwrap());
+        let err = base_url_of(&config, "independent-repository", &source, "main").unwrap_err();
+        assert!(
+            matches!(&err, DataError::Config(msg) if msg.contains("name")),
+            "{err}"
+        );
+        let source =
+            toml::Value::Table(toml::from_str("service = \"svn\"\nname = \"a/b\"").unwrap());
+        let err = base_url_of(&config, "independent-repository", &source, "main").unwrap_err();
+        assert!(matches!(err, DataError::Config(_)));
+        let err = base_url_of(&config, "ftp", &source, "main").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid data source config: Unknown load mode `ftp`"
+        );
+    }
+}
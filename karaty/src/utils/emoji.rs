@@ -0,0 +1,288 @@
+// GitHub style emoji shortcodes (e.g. `:tada:`), names of solid icons (`:bug:`, `:heart:` ...)
+// are not included, they are still displayed as icons.
+
+const EMOJI: [(&str, &str); 164] = [
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("airplane", "✈️"),
+    ("alarm_clock", "⏰"),
+    ("alien", "👽"),
+    ("angry", "😠"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrow_up", "⬆️"),
+    ("art", "🎨"),
+    ("balloon", "🎈"),
+    ("bangbang", "‼️"),
+    ("beer", "🍺"),
+    ("blue_heart", "💙"),
+    ("blush", "😊"),
+    ("bookmark", "🔖"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("bow", "🙇"),
+    ("brain", "🧠"),
+    ("broken_heart", "💔"),
+    ("bulb", "💡"),
+    ("cake", "🍰"),
+    ("calendar", "📆"),
+    ("camera", "📷"),
+    ("cat", "🐱"),
+    ("chart_with_upwards_trend", "📈"),
+    ("checkered_flag", "🏁"),
+    ("clap", "👏"),
+    ("clown_face", "🤡"),
+    ("coffee", "☕"),
+    ("computer", "💻"),
+    ("confetti_ball", "🎊"),
+    ("confused", "😕"),
+    ("construction", "🚧"),
+    ("crab", "🦀"),
+    ("crown", "👑"),
+    ("cry", "😢"),
+    ("dart", "🎯"),
+    ("dog", "🐶"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    ("email", "📧"),
+    ("exclamation", "❗"),
+    ("expressionless", "😑"),
+    ("eyes", "👀"),
+    ("facepalm", "🤦"),
+    ("fire", "🔥"),
+    ("flushed", "😳"),
+    ("four_leaf_clover", "🍀"),
+    ("gem", "💎"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("green_heart", "💚"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("hammer", "🔨"),
+    ("handshake", "🤝"),
+    ("headphones", "🎧"),
+    ("heart_eyes", "😍"),
+    ("heavy_check_mark", "✔️"),
+    ("herb", "🌿"),
+    ("hourglass", "⌛"),
+    ("hugs", "🤗"),
+    ("innocent", "😇"),
+    ("iphone", "📱"),
+    ("joy", "😂"),
+    ("key", "🔑"),
+    ("keyboard", "⌨️"),
+    ("kissing_heart", "😘"),
+    ("laughing", "😆"),
+    ("link", "🔗"),
+    ("lock", "🔒"),
+    ("mag", "🔍"),
+    ("mailbox", "📫"),
+    ("mask", "😷"),
+    ("medal_sports", "🏅"),
+    ("memo", "📝"),
+    ("money_with_wings", "💸"),
+    ("moneybag", "💰"),
+    ("movie_camera", "🎥"),
+    ("muscle", "💪"),
+    ("nerd_face", "🤓"),
+    ("neutral_face", "😐"),
+    ("new", "🆕"),
+    ("no_entry", "⛔"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("package", "📦"),
+    ("paperclip", "📎"),
+    ("partying_face", "🥳"),
+    ("pencil2", "✏️"),
+    ("penguin", "🐧"),
+    ("pensive", "😔"),
+    ("pizza", "🍕"),
+    ("point_down", "👇"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up", "👆"),
+    ("poop", "💩"),
+    ("pray", "🙏"),
+    ("purple_heart", "💜"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rage", "😡"),
+    ("rainbow", "🌈"),
+    ("raised_hands", "🙌"),
+    ("recycle", "♻️"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("roll_eyes", "🙄"),
+    ("rose", "🌹"),
+    ("running", "🏃"),
+    ("scream", "😱"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("shrug", "🤷"),
+    ("shushing_face", "🤫"),
+    ("skull", "💀"),
+    ("sleeping", "😴"),
+    ("slightly_smiling_face", "🙂"),
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("snake", "🐍"),
+    ("snowflake", "❄️"),
+    ("sob", "😭"),
+    ("sos", "🆘"),
+    ("sparkles", "✨"),
+    ("sparkling_heart", "💖"),
+    ("speech_balloon", "💬"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("stuck_out_tongue", "😛"),
+    ("sunflower", "🌻"),
+    ("sunglasses", "😎"),
+    ("sweat", "😓"),
+    ("sweat_smile", "😅"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thought_balloon", "💭"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("trophy", "🏆"),
+    ("umbrella", "☔"),
+    ("unamused", "😒"),
+    ("unlock", "🔓"),
+    ("upside_down_face", "🙃"),
+    ("v", "✌️"),
+    ("video_game", "🎮"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wink", "😉"),
+    ("worried", "😟"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("yellow_heart", "💛"),
+    ("yum", "😋"),
+    ("zap", "⚡"),
+    ("zipper_mouth_face", "🤐"),
+    ("zzz", "💤"),
+];
+
+/// unicode emoji of the shortcode name (without colons).
+pub fn emoji(name: &str) -> Option<&'static str> {
+    EMOJI
+        .binary_search_by(|(code, _)| (*code).cmp(name))
+        .ok()
+        .map(|index| EMOJI[index].1)
+}
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-')
+}
+
+/// replace shortcodes in plain text, unknown codes are kept.
+pub fn replace_shortcodes(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        let after = &rest[start + 1..];
+        let end = after.find(|c: char| !is_shortcode_char(c));
+        match end {
+            Some(end) if end > 0 && after[end..].starts_with(':') => {
+                if let Some(emoji) = emoji(&after[..end]) {
+                    result.push_str(&rest[..start]);
+                    result.push_str(emoji);
+                    rest = &after[end + 1..];
+                } else {
+                    // the closing colon may start another shortcode
+                    result.push_str(&rest[..start + 1 + end]);
+                    rest = &after[end..];
+                }
+            }
+            _ => {
+                result.push_str(&rest[..start + 1]);
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// replace shortcodes in rendered html, tags and content of `code` & `pre` are kept.
+pub fn replace_in_html(html: &str) -> String {
+    let mut result = String::new();
+    let mut rest = html;
+    let mut code_depth = 0_usize;
+    while let Some(start) = rest.find('<') {
+        if code_depth == 0 {
+            result.push_str(&replace_shortcodes(&rest[..start]));
+        } else {
+            result.push_str(&rest[..start]);
+        }
+        let end = rest[start..]
+            .find('>')
+            .map(|v| start + v + 1)
+            .unwrap_or(rest.len());
+        let tag = &rest[start..end];
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| c.is_whitespace() || matches!(c, '>' | '/'))
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if name == "code" || name == "pre" {
+            if tag.starts_with("</") {
+                code_depth = code_depth.saturating_sub(1);
+            } else {
+                code_depth += 1;
+            }
+        }
+        result.push_str(tag);
+        rest = &rest[end..];
+    }
+    if code_depth == 0 {
+        result.push_str(&replace_shortcodes(rest));
+    } else {
+        result.push_str(rest);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji_list_is_sorted() {
+        assert!(EMOJI.windows(2).all(|v| v[0].0 < v[1].0));
+        assert_eq!(emoji("tada"), Some("🎉"));
+        assert_eq!(emoji("+1"), Some("👍"));
+        assert_eq!(emoji("unknown"), None);
+    }
+
+    #[test]
+    fn shortcodes_in_text() {
+        assert_eq!(
+            replace_shortcodes("Ship it :rocket::tada:!"),
+            "Ship it 🚀🎉!"
+        );
+        assert_eq!(
+            replace_shortcodes("at 10:30 :nope: :smile:"),
+            "at 10:30 :nope: 😄"
+        );
+        assert_eq!(replace_shortcodes("a:b:smile:"), "a:b😄");
+    }
+
+    #[test]
+    fn shortcodes_in_code_are_kept() {
+        assert_eq!(
+            replace_in_html(
+                "<p>:smile: <code>:smile:</code></p><pre><code>:tada:</code></pre>:+1:"
+            ),
+            "<p>😄 <code>:smile:</code></p><pre><code>:tada:</code></pre>👍"
+        );
+        let html = crate::utils::markdown::parse_markdown(":smile: `:smile:`", false).unwrap();
+        assert_eq!(html, "<p>😄 <code>:smile:</code></p>");
+    }
+}
@@ -0,0 +1,150 @@
+//! RSS 2.0 & Atom feed generator, this module is used by `build.rs`.
+//!
+//! karaty is a client-side app, so the feed is generated as a static file at build time.
+
+#[derive(Debug, Clone)]
+pub struct FeedChannel {
+    pub title: String,
+    pub link: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    pub title: String,
+    pub link: String,
+    /// `YYYY-MM-DD` format date
+    pub date: String,
+    pub description: String,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn parse_date(date: &str) -> Option<(i64, u32, u32)> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.get(0..2)?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+/// days since 1970-01-01, use for weekday calculation.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// RFC 822 date for RSS, e.g. `Fri, 09 Sep 2022 00:00:00 +0000`
+fn rfc822(date: &str) -> Option<String> {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let (year, month, day) = parse_date(date)?;
+    let weekday = WEEKDAYS[days_from_civil(year, month, day).rem_euclid(7) as usize];
+    Some(format!(
+        "{weekday}, {day:02} {} {year:04} 00:00:00 +0000",
+        MONTHS[month as usize - 1]
+    ))
+}
+
+/// RFC 3339 date for Atom, e.g. `2022-09-09T00:00:00Z`
+fn rfc3339(date: &str) -> Option<String> {
+    let (year, month, day) = parse_date(date)?;
+    Some(format!("{year:04}-{month:02}-{day:02}T00:00:00Z"))
+}
+
+/// sort items by date, newest first.
+pub fn sort_items(items: &mut [FeedItem]) {
+    items.sort_by_key(|b| std::cmp::Reverse(parse_date(&b.date)));
+}
+
+pub fn rss(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let mut result = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    result.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+    result.push_str("<channel>\n");
+    result.push_str(&format!("<title>{}</title>\n", escape(&channel.title)));
+    result.push_str(&format!("<link>{}</link>\n", escape(&channel.link)));
+    result.push_str(&format!(
+        "<description>{}</description>\n",
+        escape(&channel.description)
+    ));
+    result.push_str(&format!(
+        "<atom:link href=\"{}/feed.xml\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape(channel.link.trim_end_matches('/'))
+    ));
+    for item in items {
+        result.push_str("<item>\n");
+        result.push_str(&format!("<title>{}</title>\n", escape(&item.title)));
+        result.push_str(&format!("<link>{}</link>\n", escape(&item.link)));
+        result.push_str(&format!("<guid>{}</guid>\n", escape(&item.link)));
+        if let Some(date) = rfc822(&item.date) {
+            result.push_str(&format!("<pubDate>{date}</pubDate>\n"));
+        }
+        if !item.description.is_empty() {
+            result.push_str(&format!(
+                "<description>{}</description>\n",
+                escape(&item.description)
+            ));
+        }
+        result.push_str("</item>\n");
+    }
+    result.push_str("</channel>\n</rss>\n");
+    result
+}
+
+pub fn atom(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let updated = items
+        .iter()
+        .filter_map(|v| rfc3339(&v.date))
+        .max()
+        .unwrap_or(String::from("1970-01-01T00:00:00Z"));
+    let link = escape(channel.link.trim_end_matches('/'));
+
+    let mut result = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    result.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    result.push_str(&format!("<title>{}</title>\n", escape(&channel.title)));
+    result.push_str(&format!(
+        "<subtitle>{}</subtitle>\n",
+        escape(&channel.description)
+    ));
+    result.push_str(&format!("<link href=\"{link}/\"/>\n"));
+    result.push_str(&format!("<link href=\"{link}/atom.xml\" rel=\"self\"/>\n"));
+    result.push_str(&format!("<id>{link}/</id>\n"));
+    result.push_str(&format!("<updated>{updated}</updated>\n"));
+    result.push_str(&format!(
+        "<author><name>{}</name></author>\n",
+        escape(&channel.title)
+    ));
+    for item in items {
+        let date = rfc3339(&item.date).unwrap_or(updated.clone());
+        result.push_str("<entry>\n");
+        result.push_str(&format!("<title>{}</title>\n", escape(&item.title)));
+        result.push_str(&format!("<link href=\"{}\"/>\n", escape(&item.link)));
+        result.push_str(&format!("<id>{}</id>\n", escape(&item.link)));
+        result.push_str(&format!("<updated>{date}</updated>\n"));
+        if !item.description.is_empty() {
+            result.push_str(&format!(
+                "<summary>{}</summary>\n",
+                escape(&item.description)
+            ));
+        }
+        result.push_str("</entry>\n");
+    }
+    result.push_str("</feed>\n");
+    result
+}
@@ -0,0 +1,159 @@
+// document head helpers, all values are escaped as js string.
+
+fn js_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// update document title.
+pub fn set_title(title: &str) {
+    let _ = js_sys::eval(&format!("document.title = {};", js_str(title)));
+}
+
+/// insert or update a `<meta>` tag, `attr` is `name` or `property`.
+pub fn set_meta(attr: &str, key: &str, content: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(attr, key, content) {{
+            var el = document.head.querySelector('meta[' + attr + '=\"' + key + '\"]');
+            if (el == null) {{
+                el = document.createElement('meta');
+                el.setAttribute(attr, key);
+                document.head.appendChild(el);
+            }}
+            el.setAttribute('content', content);
+        }})({}, {}, {});",
+        js_str(attr),
+        js_str(key),
+        js_str(content)
+    ));
+}
+
+/// update the href of favicon `<link>` tag, the tag will be created if it doesn't exist.
+pub fn set_favicon(href: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(href) {{
+            var list = document.head.querySelectorAll('link[rel~=\"icon\"]');
+            if (list.length == 0) {{
+                var el = document.createElement('link');
+                el.setAttribute('rel', 'icon');
+                document.head.appendChild(el);
+                list = [el];
+            }}
+            for (var i = 0; i < list.length; i++) {{ list[i].setAttribute('href', href); }}
+        }})({});",
+        js_str(href)
+    ));
+}
+
+/// append a deferred `<script>` tag with attributes, the same `src` will only be added once.
+pub fn add_script(src: &str, attributes: &[(&str, &str)]) {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| format!("el.setAttribute({}, {});", js_str(key), js_str(value)))
+        .collect::<String>();
+    let _ = js_sys::eval(&format!(
+        "(function(src) {{
+            if (document.head.querySelector('script[src=\"' + src + '\"]') != null) {{ return; }}
+            var el = document.createElement('script');
+            el.defer = true;
+            el.src = src;
+            {}
+            document.head.appendChild(el);
+        }})({});",
+        attributes,
+        js_str(src)
+    ));
+}
+
+/// append a stylesheet `<link>` tag with attributes, the same `href` will only be added once.
+pub fn add_stylesheet(href: &str, attributes: &[(&str, &str)]) {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| format!("el.setAttribute({}, {});", js_str(key), js_str(value)))
+        .collect::<String>();
+    let _ = js_sys::eval(&format!(
+        "(function(href) {{
+            if (document.head.querySelector('link[href=\"' + href + '\"]') != null) {{ return; }}
+            var el = document.createElement('link');
+            el.rel = 'stylesheet';
+            {}
+            el.href = href;
+            document.head.appendChild(el);
+        }})({});",
+        attributes,
+        js_str(href)
+    ));
+}
+
+/// add a stylesheet `<link>` with `id`, or update its `href` if it exists.
+pub fn set_stylesheet(id: &str, href: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(id, href) {{
+            var el = document.getElementById(id);
+            if (el == null) {{
+                el = document.createElement('link');
+                el.id = id;
+                el.rel = 'stylesheet';
+                document.head.appendChild(el);
+            }}
+            if (el.getAttribute('href') != href) {{ el.href = href; }}
+        }})({}, {});",
+        js_str(id),
+        js_str(href)
+    ));
+}
+
+/// append a `<style>` tag with css rules, the tag with the same `id` will be replaced.
+pub fn add_style(id: &str, css: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(id, css) {{
+            var el = document.getElementById(id);
+            if (el != null) {{ el.remove(); }}
+            el = document.createElement('style');
+            el.id = id;
+            el.textContent = css;
+            document.head.appendChild(el);
+        }})({}, {});",
+        js_str(id),
+        js_str(css)
+    ));
+}
+
+/// remove a `<meta>` tag if it exists.
+pub fn remove_meta(attr: &str, key: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(attr, key) {{
+            var el = document.head.querySelector('meta[' + attr + '=\"' + key + '\"]');
+            if (el != null) {{ el.remove(); }}
+        }})({}, {});",
+        js_str(attr),
+        js_str(key)
+    ));
+}
+
+/// update open graph & twitter card meta tags, image tags will be removed when no image.
+pub fn set_social_meta(title: &str, description: Option<&str>, image: Option<&str>) {
+    set_meta("property", "og:title", title);
+    set_meta("name", "twitter:title", title);
+    match description {
+        Some(description) => {
+            set_meta("property", "og:description", description);
+            set_meta("name", "twitter:description", description);
+        }
+        None => {
+            remove_meta("property", "og:description");
+            remove_meta("name", "twitter:description");
+        }
+    }
+    match image {
+        Some(image) => {
+            set_meta("property", "og:image", image);
+            set_meta("name", "twitter:image", image);
+            set_meta("name", "twitter:card", "summary_large_image");
+        }
+        None => {
+            remove_meta("property", "og:image");
+            remove_meta("name", "twitter:image");
+            set_meta("name", "twitter:card", "summary");
+        }
+    }
+}
@@ -1,16 +1,827 @@
-use markdown::{CompileOptions, Options, ParseOptions};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-#[allow(dead_code)]
-pub fn parse_markdown(content: &str) -> Option<String> {
-    markdown::to_html_with_options(
-        content,
-        &Options {
-            parse: ParseOptions::gfm(),
-            compile: CompileOptions {
-                allow_dangerous_html: true,
-                ..CompileOptions::gfm()
+use karaty_blueprint::front_matter::{meta_string, parse_front_matter};
+use markdown::{
+    mdast::{AlignKind, Node, Table},
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+
+use super::{emoji::replace_in_html, sanitize::sanitize_html_with, slug::slugify};
+
+/// markdown renderer, components render content through the one stored in context,
+/// so the pipeline can be replaced (e.g. mocked) without touching components.
+pub trait Renderer {
+    /// render markdown to (sanitized) html.
+    fn render(&self, input: &str) -> anyhow::Result<String>;
+
+    /// parse markdown to mdast, renderers without mdast support return `None`
+    /// and their html output will be used.
+    fn parse(&self, _input: &str) -> Option<Node> {
+        None
+    }
+}
+
+/// default renderer based on `markdown` crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownRenderer {
+    /// enable github flavored extensions (tables, task lists, footnotes ...)
+    pub gfm: bool,
+    /// keep raw html in content (sanitized)
+    pub allow_html: bool,
+    /// parse `$...$` & `$$...$$` as math
+    pub math: bool,
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        Self {
+            gfm: true,
+            allow_html: false,
+            math: false,
+        }
+    }
+}
+
+impl MarkdownRenderer {
+    /// create renderer by `markdown-flavor` config: `gfm` (default) or `commonmark`.
+    pub fn from_flavor(flavor: Option<&str>) -> Self {
+        let gfm = !matches!(flavor, Some(v) if v.eq_ignore_ascii_case("commonmark"));
+        Self {
+            gfm,
+            ..Default::default()
+        }
+    }
+
+    fn parse_options(&self) -> ParseOptions {
+        let mut options = if self.gfm {
+            ParseOptions::gfm()
+        } else {
+            ParseOptions::default()
+        };
+        // dollar signs in code are never parsed as math
+        options.constructs.math_text = self.math;
+        options.constructs.math_flow = self.math;
+        options
+    }
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, input: &str) -> anyhow::Result<String> {
+        let compile = if self.gfm {
+            CompileOptions::gfm()
+        } else {
+            CompileOptions::default()
+        };
+        let html = markdown::to_html_with_options(
+            input,
+            &Options {
+                parse: self.parse_options(),
+                compile: CompileOptions {
+                    allow_dangerous_html: self.allow_html,
+                    gfm_footnote_clobber_prefix: Some(format!("{}-", next_footnote_prefix())),
+                    ..compile
+                },
             },
+        )
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let html = sanitize_html_with(&html, false, &|_| None);
+        Ok(replace_in_html(&html))
+    }
+
+    fn parse(&self, input: &str) -> Option<Node> {
+        markdown::to_mdast(input, &self.parse_options()).ok()
+    }
+}
+
+/// renderer stored in context, see [`Renderer`].
+#[derive(Clone)]
+pub struct SharedRenderer(pub Rc<dyn Renderer>);
+
+impl Default for SharedRenderer {
+    fn default() -> Self {
+        Self(Rc::new(MarkdownRenderer::default()))
+    }
+}
+
+/// render markdown to html, raw html will be escaped unless `allow_html` is enabled,
+/// the output is always sanitized.
+#[allow(dead_code)]
+pub fn parse_markdown(content: &str, allow_html: bool) -> Option<String> {
+    MarkdownRenderer {
+        allow_html,
+        ..Default::default()
+    }
+    .render(content)
+    .ok()
+}
+
+/// resolve relative url (e.g. `./img/foo.png`) to an absolute url under `base`,
+/// `dir` is the directory of current page in data source.
+/// absolute url, root path & anchor will return None.
+pub fn resolve_relative_url(base: &str, dir: &str, url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() || url.starts_with(['/', '#', '?', '@']) {
+        return None;
+    }
+    let scheme_end = url.find(':');
+    let path_start = url.find(['/', '?', '#']);
+    if let Some(colon) = scheme_end {
+        if path_start.map(|path| colon < path).unwrap_or(true) {
+            return None;
+        }
+    }
+
+    let mut segments = dir
+        .split('/')
+        .filter(|v| !v.is_empty() && *v != ".")
+        .collect::<Vec<&str>>();
+    for segment in url.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    if url.ends_with('/') {
+        segments.push("");
+    }
+    Some(format!("{}/{}", base.trim_end_matches('/'), segments.join("/")))
+}
+
+/// heading info: (level, text, id, start offset)
+type HeadingInfo = (u8, String, String, usize);
+
+fn collect_headings(
+    nodes: &[Node],
+    used: &mut HashMap<String, usize>,
+    result: &mut Vec<HeadingInfo>,
+) {
+    for node in nodes {
+        if let Node::Heading(h) = node {
+            let text = node.to_string();
+            let mut id = slugify(&text);
+            if id.is_empty() {
+                id = String::from("section");
+            }
+            // append `-2`, `-3` ... until the id is unique in current page
+            let base = id.clone();
+            let mut count = *used.get(&base).unwrap_or(&0);
+            while used.contains_key(&id) {
+                count += 1;
+                id = format!("{base}-{count}");
+            }
+            used.insert(base, count.max(1));
+            used.insert(id.clone(), 1);
+            let offset = h.position.as_ref().map(|p| p.start.offset).unwrap_or_default();
+            result.push((h.depth, text, id, offset));
+        } else if let Some(children) = node.children() {
+            collect_headings(children, used, result);
+        }
+    }
+}
+
+/// generate unique anchor id for every heading, key is the heading start offset.
+pub fn heading_ids(nodes: &[Node]) -> HashMap<usize, String> {
+    let mut result = vec![];
+    collect_headings(nodes, &mut HashMap::new(), &mut result);
+    result
+        .into_iter()
+        .map(|(_, _, id, offset)| (offset, id))
+        .collect()
+}
+
+/// table of contents (h1 - h4) from markdown nodes: (level, text, id)
+pub fn table_of_contents(nodes: &[Node]) -> Vec<(u8, String, String)> {
+    let mut result = vec![];
+    collect_headings(nodes, &mut HashMap::new(), &mut result);
+    result
+        .into_iter()
+        .filter(|(level, _, _, _)| *level <= 4)
+        .map(|(level, text, id, _)| (level, text, id))
+        .collect()
+}
+
+fn collect_text(nodes: &[Node], result: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Yaml(_) | Node::Toml(_) | Node::Html(_) => {}
+            Node::Text(v) => result.push_str(&v.value),
+            Node::InlineCode(v) => result.push_str(&v.value),
+            Node::Code(v) => result.push_str(&v.value),
+            _ => {
+                if let Some(children) = node.children() {
+                    collect_text(children, result);
+                }
+            }
+        }
+        result.push(' ');
+    }
+}
+
+/// get visible plain text and title (front matter `title` or first heading) from markdown.
+pub fn plain_text(content: &str) -> (Option<String>, String) {
+    let options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            ..Constructs::gfm()
         },
+        ..ParseOptions::gfm()
+    };
+    let mut text = String::new();
+    let mut title = parse_front_matter(content).and_then(|(meta, _)| meta_string(&meta, "title"));
+    if let Ok(Node::Root(root)) = markdown::to_mdast(content, &options) {
+        collect_text(&root.children, &mut text);
+        if title.is_none() {
+            title = root.children.iter().find_map(|node| match node {
+                Node::Heading(_) => Some(node.to_string()),
+                _ => None,
+            });
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    (title, text)
+}
+
+fn count_words(nodes: &[Node]) -> usize {
+    let mut count = 0;
+    for node in nodes {
+        match node {
+            Node::Yaml(_) | Node::Toml(_) | Node::Code(_) | Node::Html(_) => {}
+            Node::Text(v) => count += v.value.split_whitespace().count(),
+            Node::InlineCode(v) => count += v.value.split_whitespace().count(),
+            _ => {
+                if let Some(children) = node.children() {
+                    count += count_words(children);
+                }
+            }
+        }
+    }
+    count
+}
+
+/// count words in markdown, code blocks & front matter are excluded.
+pub fn word_count(content: &str) -> usize {
+    let options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    match markdown::to_mdast(content, &options) {
+        Ok(Node::Root(root)) => count_words(&root.children),
+        _ => 0,
+    }
+}
+
+/// estimate reading time in minutes by `wpm` (words per minute), at least 1 minute.
+pub fn reading_time(content: &str, wpm: usize) -> usize {
+    let wpm = wpm.max(1);
+    let words = word_count(content);
+    words.div_ceil(wpm).max(1)
+}
+
+/// footnotes of one markdown content, numbered by the order of their first reference (like GFM).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Footnotes {
+    /// footnote identifier => number
+    pub numbers: HashMap<String, usize>,
+    /// start offset of the first reference to every footnote, back links point to it.
+    pub first_refs: HashSet<usize>,
+    /// content of referenced footnotes, ordered by number.
+    pub definitions: Vec<Vec<Node>>,
+}
+
+/// counter of rendered markdown contents, use for unique footnote ids.
+static MARKDOWN_INSTANCES: AtomicUsize = AtomicUsize::new(0);
+
+/// footnote id prefix of a new markdown content, unique in the page.
+pub fn next_footnote_prefix() -> String {
+    format!("md{}", MARKDOWN_INSTANCES.fetch_add(1, Ordering::Relaxed))
+}
+
+/// ids of footnote `number` under `prefix`: (definition id, first reference id).
+pub fn footnote_ids(prefix: &str, number: usize) -> (String, String) {
+    (
+        format!("{prefix}-fn-{number}"),
+        format!("{prefix}-fnref-{number}"),
     )
-    .ok()
+}
+
+fn collect_footnote_definitions(nodes: &[Node], result: &mut HashMap<String, Vec<Node>>) {
+    for node in nodes {
+        if let Node::FootnoteDefinition(def) = node {
+            // the first definition wins, like link definitions
+            result
+                .entry(def.identifier.clone())
+                .or_insert_with(|| def.children.clone());
+        } else if let Some(children) = node.children() {
+            collect_footnote_definitions(children, result);
+        }
+    }
+}
+
+fn collect_footnote_refs(
+    nodes: &[Node],
+    defined: &HashMap<String, Vec<Node>>,
+    order: &mut Vec<String>,
+    first_refs: &mut HashSet<usize>,
+) {
+    for node in nodes {
+        match node {
+            Node::FootnoteReference(r) => {
+                if defined.contains_key(&r.identifier) && !order.contains(&r.identifier) {
+                    order.push(r.identifier.clone());
+                    if let Some(position) = &r.position {
+                        first_refs.insert(position.start.offset);
+                    }
+                }
+            }
+            // definitions are visited after content, see `footnotes`
+            Node::FootnoteDefinition(_) => {}
+            _ => {
+                if let Some(children) = node.children() {
+                    collect_footnote_refs(children, defined, order, first_refs);
+                }
+            }
+        }
+    }
+}
+
+/// collect footnotes (`[^1]`) from markdown nodes, unreferenced definitions are dropped.
+pub fn footnotes(nodes: &[Node]) -> Footnotes {
+    let mut defined = HashMap::new();
+    collect_footnote_definitions(nodes, &mut defined);
+    let mut order = vec![];
+    let mut first_refs = HashSet::new();
+    collect_footnote_refs(nodes, &defined, &mut order, &mut first_refs);
+    // footnotes referenced by other footnotes are numbered after them
+    let mut index = 0;
+    while index < order.len() {
+        if let Some(children) = defined.get(&order[index]) {
+            collect_footnote_refs(children, &defined, &mut order, &mut first_refs);
+        }
+        index += 1;
+    }
+    Footnotes {
+        numbers: order
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (id.clone(), index + 1))
+            .collect(),
+        first_refs,
+        definitions: order
+            .iter()
+            .filter_map(|id| defined.get(id).cloned())
+            .collect(),
+    }
+}
+
+/// table cell content & its `text-align` style (empty when the column has no alignment).
+pub type TableCell = (Vec<Node>, &'static str);
+
+/// GFM table split into the header row (the first row) and body rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableLayout {
+    pub head: Option<Vec<TableCell>>,
+    pub body: Vec<Vec<TableCell>>,
+}
+
+fn align_style(align: Option<&AlignKind>) -> &'static str {
+    match align {
+        Some(AlignKind::Left) => "text-align: left",
+        Some(AlignKind::Right) => "text-align: right",
+        Some(AlignKind::Center) => "text-align: center",
+        _ => "",
+    }
+}
+
+/// split table rows into header & body, cells are aligned by the column of `table.align`.
+pub fn table_layout(table: &Table) -> TableLayout {
+    let mut rows = table.children.iter().map(|row| {
+        row.children()
+            .map(|cells| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cell)| {
+                        let children = cell.children().cloned().unwrap_or_default();
+                        (children, align_style(table.align.get(i)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    });
+    TableLayout {
+        head: rows.next(),
+        body: rows.collect(),
+    }
+}
+
+/// markdown node, or nodes wrapped by `<details>` & `</details>` html blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MdastBlock {
+    Node(Node),
+    Details {
+        open: bool,
+        /// raw html of `<summary>` content
+        summary: Option<String>,
+        children: Vec<Node>,
+    },
+}
+
+/// html block which opens a `<details>` without closing it: (open, summary).
+fn details_start(node: &Node) -> Option<(bool, Option<String>)> {
+    let Node::Html(html) = node else {
+        return None;
+    };
+    let value = html.value.trim();
+    let lower = value.to_lowercase();
+    if !lower.starts_with("<details") || lower.contains("</details>") {
+        return None;
+    }
+    let tag_end = lower.find('>')?;
+    let open = lower[..tag_end]
+        .split_whitespace()
+        .any(|attr| attr.trim_end_matches('/') == "open");
+    // lowercase keeps byte offsets for ascii tags
+    let summary = lower
+        .find("<summary>")
+        .zip(lower.find("</summary>"))
+        .filter(|(start, end)| start < end)
+        .map(|(start, end)| value[start + "<summary>".len()..end].trim().to_string());
+    Some((open, summary))
+}
+
+fn is_details_end(node: &Node) -> bool {
+    matches!(node, Node::Html(html) if html.value.trim().eq_ignore_ascii_case("</details>"))
+}
+
+/// group markdown between `<details>` & `</details>` html blocks (separated by blank lines),
+/// so the content can be rendered inside the collapsible. unclosed `<details>` wraps the rest nodes.
+pub fn group_details(nodes: &[Node]) -> Vec<MdastBlock> {
+    let mut result = vec![];
+    let mut index = 0;
+    while index < nodes.len() {
+        let Some((open, summary)) = details_start(&nodes[index]) else {
+            result.push(MdastBlock::Node(nodes[index].clone()));
+            index += 1;
+            continue;
+        };
+        let mut depth = 1;
+        let mut end = index + 1;
+        while end < nodes.len() {
+            if details_start(&nodes[end]).is_some() {
+                depth += 1;
+            } else if is_details_end(&nodes[end]) {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            end += 1;
+        }
+        result.push(MdastBlock::Details {
+            open,
+            summary,
+            children: nodes[index + 1..end].to_vec(),
+        });
+        index = end + 1;
+    }
+    result
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// serialize phrasing nodes which contain inline raw html (e.g. `<kbd>Ctrl</kbd>`) to one html string,
+/// each `Html` node is only a single tag, so they can't be rendered separately.
+/// the output is not sanitized.
+pub fn inline_html(nodes: &[Node]) -> String {
+    let mut result = String::new();
+    for node in nodes {
+        match node {
+            Node::Html(html) => result.push_str(&html.value),
+            Node::Text(text) => result.push_str(&escape_html(&text.value)),
+            Node::InlineCode(code) => {
+                result.push_str(&format!("<code>{}</code>", escape_html(&code.value)));
+            }
+            Node::Break(_) => result.push_str("<br />"),
+            Node::Emphasis(v) => result.push_str(&format!("<em>{}</em>", inline_html(&v.children))),
+            Node::Strong(v) => {
+                result.push_str(&format!("<strong>{}</strong>", inline_html(&v.children)));
+            }
+            Node::Delete(v) => result.push_str(&format!("<del>{}</del>", inline_html(&v.children))),
+            Node::Link(link) => {
+                let title = link
+                    .title
+                    .as_ref()
+                    .map(|v| format!(" title=\"{}\"", escape_html(v)))
+                    .unwrap_or_default();
+                result.push_str(&format!(
+                    "<a href=\"{}\"{title}>{}</a>",
+                    escape_html(&link.url),
+                    inline_html(&link.children)
+                ));
+            }
+            Node::Image(image) => {
+                result.push_str(&format!(
+                    "<img src=\"{}\" alt=\"{}\" />",
+                    escape_html(&image.url),
+                    escape_html(&image.alt)
+                ));
+            }
+            other => result.push_str(&escape_html(&other.to_string())),
+        }
+    }
+    result
+}
+
+/// supported `> [!TYPE]` callout types.
+pub const ADMONITION_TYPES: [&str; 6] = ["note", "tip", "important", "warning", "caution", "danger"];
+
+/// github style callout: blockquote starts with `[!NOTE]`, `[!TIP]`, `[!WARNING]` ...
+/// returns the (lowercase) type and the content without marker, `None` for unknown type.
+pub fn admonition(children: &[Node]) -> Option<(String, Vec<Node>)> {
+    let Some(Node::Paragraph(first)) = children.first() else {
+        return None;
+    };
+    let Some(Node::Text(text)) = first.children.first() else {
+        return None;
+    };
+    let rest = text.value.strip_prefix("[!")?;
+    let (kind, rest) = rest.split_once(']')?;
+    let kind = kind.to_lowercase();
+    if !ADMONITION_TYPES.contains(&kind.as_str()) {
+        return None;
+    }
+    // marker is on its own line, content may follow in the same paragraph
+    let rest = rest.trim_start_matches([' ', '\t']);
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+
+    let mut paragraph = first.clone();
+    if rest.is_empty() {
+        paragraph.children.remove(0);
+        // soft break after the marker
+        if let Some(Node::Break(_)) = paragraph.children.first() {
+            paragraph.children.remove(0);
+        }
+    } else if let Some(Node::Text(text)) = paragraph.children.first_mut() {
+        text.value = rest.to_string();
+    }
+
+    let mut content = vec![];
+    if !paragraph.children.is_empty() {
+        content.push(Node::Paragraph(paragraph));
+    }
+    content.extend(children[1..].iter().cloned());
+    Some((kind, content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gfm_table() {
+        let html = parse_markdown("| a | b |\n| - | - |\n| 1 | 2 |", false).unwrap();
+        assert!(html.contains("<table>"), "{html}");
+        assert!(html.contains("<th>a</th>"), "{html}");
+        assert!(html.contains("<td>2</td>"), "{html}");
+    }
+
+    #[test]
+    fn gfm_strikethrough_and_autolink() {
+        let html = parse_markdown("~~old~~ www.example.com", false).unwrap();
+        assert!(html.contains("<del>old</del>"), "{html}");
+        assert!(
+            html.contains("<a href=\"http://www.example.com\">"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn gfm_task_list() {
+        let html = parse_markdown("- [x] done\n- [ ] todo", false).unwrap();
+        assert!(html.contains("<input type=\"checkbox\""), "{html}");
+    }
+
+    #[test]
+    fn inline_html_keeps_tags_in_paragraph() {
+        let root = MarkdownRenderer::default()
+            .parse("Press <kbd>Ctrl</kbd> + **<sup>1</sup>** & `<b>`")
+            .unwrap();
+        let Some(Node::Paragraph(paragraph)) = root.children().and_then(|v| v.first()) else {
+            panic!("paragraph expected");
+        };
+        assert_eq!(
+            inline_html(&paragraph.children),
+            "Press <kbd>Ctrl</kbd> + <strong><sup>1</sup></strong> &amp; <code>&lt;b&gt;</code>"
+        );
+    }
+
+    fn table(content: &str) -> Table {
+        match nodes(content).into_iter().next() {
+            Some(Node::Table(table)) => table,
+            other => panic!("table expected: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn table_layout_splits_header_and_aligns_columns() {
+        let layout = table_layout(&table(
+            "| a | b | c | d |\n| :- | :-: | -: | - |\n| 1 | 2 | 3 | 4 |",
+        ));
+        // (text, style) of every cell
+        let cells = |row: &Vec<TableCell>| {
+            row.iter()
+                .map(|(children, style)| {
+                    let text = children.iter().map(|v| v.to_string()).collect::<String>();
+                    (text, *style)
+                })
+                .collect::<Vec<_>>()
+        };
+        let head = layout.head.as_ref().map(cells).unwrap();
+        assert_eq!(
+            head,
+            [
+                ("a".to_string(), "text-align: left"),
+                ("b".to_string(), "text-align: center"),
+                ("c".to_string(), "text-align: right"),
+                ("d".to_string(), ""),
+            ]
+        );
+        assert_eq!(layout.body.len(), 1);
+        assert_eq!(
+            cells(&layout.body[0]),
+            [
+                ("1".to_string(), "text-align: left"),
+                ("2".to_string(), "text-align: center"),
+                ("3".to_string(), "text-align: right"),
+                ("4".to_string(), ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn table_layout_without_body() {
+        let layout = table_layout(&table("| a | b |\n| - | -: |"));
+        assert_eq!(layout.head.map(|v| v.len()), Some(2));
+        assert!(layout.body.is_empty());
+        assert_eq!(
+            table_layout(&Table {
+                children: vec![],
+                position: None,
+                align: vec![]
+            }),
+            TableLayout::default()
+        );
+    }
+
+    #[test]
+    fn commonmark_has_no_table() {
+        let html = MarkdownRenderer::from_flavor(Some("commonmark"))
+            .render("| a | b |\n| - | - |\n| 1 | 2 |")
+            .unwrap();
+        assert!(!html.contains("<table>"), "{html}");
+    }
+
+    fn nodes(content: &str) -> Vec<Node> {
+        match MarkdownRenderer::default().parse(content) {
+            Some(Node::Root(root)) => root.children,
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn heading_ids_are_unique() {
+        let toc = table_of_contents(&nodes("# Intro\n## Intro\n### Intro 2\n#### Intro\n# !!!"));
+        let ids = toc.iter().map(|(_, _, id)| id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["intro", "intro-2", "intro-2-2", "intro-3", "section"]);
+    }
+
+    #[test]
+    fn heading_ids_by_offset() {
+        let ids = heading_ids(&nodes("# Hello World\n\ntext\n\n## Hello World"));
+        assert_eq!(ids.get(&0).map(|v| v.as_str()), Some("hello-world"));
+        assert_eq!(ids.get(&21).map(|v| v.as_str()), Some("hello-world-2"));
+    }
+
+    #[test]
+    fn word_count_skips_code_and_front_matter() {
+        let content = "---\ntitle: Some Long Title\n---\n\
+            # Hello world\n\nOne `two` three.\n\n\
+            ```rust\nlet a = 1;\n```";
+        assert_eq!(word_count(content), 5);
+        assert_eq!(word_count(""), 0);
+    }
+
+    #[test]
+    fn reading_time_rounds_up() {
+        assert_eq!(reading_time(&"word ".repeat(401), 200), 3);
+        assert_eq!(reading_time(&"word ".repeat(400), 200), 2);
+        // short posts show at least 1 minute, invalid wpm is treated as 1
+        assert_eq!(reading_time("hi", 200), 1);
+        assert_eq!(reading_time("", 200), 1);
+        assert_eq!(reading_time("a b c", 0), 3);
+    }
+
+    fn blockquote(content: &str) -> Vec<Node> {
+        match nodes(content).into_iter().next() {
+            Some(Node::Blockquote(quote)) => quote.children,
+            other => panic!("blockquote expected: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn admonition_types() {
+        for kind in ADMONITION_TYPES {
+            let marker = kind.to_uppercase();
+            let children = blockquote(&format!("> [!{marker}]\n> Be careful."));
+            let (parsed, content) = admonition(&children).unwrap();
+            assert_eq!(parsed, kind);
+            assert_eq!(content.len(), 1);
+            assert_eq!(content[0].to_string(), "Be careful.");
+        }
+    }
+
+    #[test]
+    fn admonition_content_on_marker_line() {
+        let children = blockquote("> [!tip] Inline *content*\n>\n> Second paragraph.");
+        let (kind, content) = admonition(&children).unwrap();
+        assert_eq!(kind, "tip");
+        assert_eq!(content.len(), 2);
+        assert_eq!(content[0].to_string(), "Inline content");
+    }
+
+    #[test]
+    fn unknown_admonition_is_blockquote() {
+        assert!(admonition(&blockquote("> [!OTHER]\n> text")).is_none());
+        assert!(admonition(&blockquote("> plain quote")).is_none());
+    }
+
+    #[test]
+    fn footnotes_numbered_by_first_reference() {
+        let content = "A[^b] B[^a] again[^b] missing[^x].\n\n[^a]: First.\n[^b]: Second[^c].\n\
+            [^c]: Nested.\n[^unused]: Dropped.";
+        let list = footnotes(&nodes(content));
+        assert_eq!(list.numbers.get("b"), Some(&1));
+        assert_eq!(list.numbers.get("a"), Some(&2));
+        assert_eq!(list.numbers.get("c"), Some(&3));
+        assert!(!list.numbers.contains_key("x"));
+        assert!(!list.numbers.contains_key("unused"));
+        let definitions = list
+            .definitions
+            .iter()
+            .map(|v| v[0].to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(definitions, ["Second.", "First.", "Nested."]);
+        // offsets of `A[^b]` & `B[^a]`, not the second `[^b]` or the one in definition
+        let mut first_refs = list.first_refs.into_iter().collect::<Vec<_>>();
+        first_refs.sort();
+        assert_eq!(first_refs.len(), 3);
+        assert_eq!(&first_refs[..2], &[1, 7]);
+    }
+
+    #[test]
+    fn footnote_ids_are_unique_per_content() {
+        let first = next_footnote_prefix();
+        let second = next_footnote_prefix();
+        assert_ne!(first, second);
+        assert_ne!(footnote_ids(&first, 1), footnote_ids(&second, 1));
+        assert_ne!(footnote_ids(&first, 1), footnote_ids(&first, 2));
+        let (id, ref_id) = footnote_ids("md0", 1);
+        assert_eq!((id.as_str(), ref_id.as_str()), ("md0-fn-1", "md0-fnref-1"));
+    }
+
+    #[test]
+    fn footnote_html() {
+        let content = "Text[^1].\n\n[^1]: Note.";
+        let first = parse_markdown(content, false).unwrap();
+        let second = parse_markdown(content, false).unwrap();
+        assert_ne!(first, second);
+        for html in [first, second] {
+            let prefix = html
+                .split("id=\"")
+                .nth(1)
+                .and_then(|v| v.split("-fnref-1\"").next())
+                .unwrap();
+            let (id, ref_id) = footnote_ids(prefix, 1);
+            let reference = format!("<sup><a href=\"#{id}\" id=\"{ref_id}\">1</a></sup>");
+            assert!(html.contains(&reference), "{html}");
+            assert!(html.contains(&format!("<li id=\"{id}\">")), "{html}");
+            assert!(html.contains(&format!("href=\"#{ref_id}\"")), "{html}");
+        }
+    }
 }
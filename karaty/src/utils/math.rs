@@ -0,0 +1,56 @@
+// math rendering by KaTeX, nothing is loaded unless `site.math` is enabled.
+
+use super::head;
+
+const KATEX_BASE: &str = "https://cdn.jsdelivr.net/npm/katex@0.16.9/dist";
+/// subresource integrity of the pinned version, see KaTeX release notes.
+const KATEX_SCRIPT_INTEGRITY: &str =
+    "sha384-XjKyOOlGwcjNTAIQHIpgOno0Hl1YQqzUOEleOLALmuqehneUG+vnGctmUb0ZY0l8";
+const KATEX_STYLE_INTEGRITY: &str =
+    "sha384-n8MVd4RsNIU0tAv4ct0nTaAbDJwPJzDEaqSD1odI+WdtXRGWt2kTvGFasHpSy3SV";
+
+fn katex_script() -> String {
+    format!("{KATEX_BASE}/katex.min.js")
+}
+
+/// load KaTeX script & stylesheet into document head.
+pub fn load() {
+    head::add_stylesheet(
+        &format!("{KATEX_BASE}/katex.min.css"),
+        &[
+            ("integrity", KATEX_STYLE_INTEGRITY),
+            ("crossorigin", "anonymous"),
+        ],
+    );
+    head::add_script(
+        &katex_script(),
+        &[
+            ("integrity", KATEX_SCRIPT_INTEGRITY),
+            ("crossorigin", "anonymous"),
+        ],
+    );
+}
+
+/// render all `.math-inline` & `.math-display` elements which are not rendered yet,
+/// it will wait for the script when it's still loading.
+pub fn render() {
+    let _ = js_sys::eval(&format!(
+        "(function(src) {{
+            var render = function() {{
+                var list = document.querySelectorAll('.math-inline:not([data-math]), .math-display:not([data-math])');
+                for (var i = 0; i < list.length; i++) {{
+                    var el = list[i];
+                    el.setAttribute('data-math', el.textContent);
+                    katex.render(el.textContent, el, {{
+                        displayMode: el.classList.contains('math-display'),
+                        throwOnError: false
+                    }});
+                }}
+            }};
+            if (window.katex) {{ render(); return; }}
+            var el = document.head.querySelector('script[src=\"' + src + '\"]');
+            if (el != null) {{ el.addEventListener('load', render); }}
+        }})({});",
+        serde_json::to_string(&katex_script()).unwrap_or_default()
+    ));
+}
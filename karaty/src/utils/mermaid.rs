@@ -0,0 +1,49 @@
+// mermaid diagram rendering, nothing is loaded unless `site.mermaid` is enabled.
+
+use super::head;
+
+const MERMAID_SCRIPT: &str = "https://cdn.jsdelivr.net/npm/mermaid@10.9.0/dist/mermaid.min.js";
+
+/// load mermaid script into document head, `script` replaces the default CDN url,
+/// `integrity` is checked by browser when provided.
+pub fn load(script: Option<&str>, integrity: Option<&str>) {
+    let mut attributes = vec![("crossorigin", "anonymous"), ("data-karaty", "mermaid")];
+    if let Some(integrity) = integrity {
+        attributes.push(("integrity", integrity));
+    }
+    head::add_script(script.unwrap_or(MERMAID_SCRIPT), &attributes);
+}
+
+/// render all `.mermaid-diagram` elements which are not rendered yet,
+/// the source is displayed as code block when the diagram is invalid.
+pub fn render() {
+    let _ = js_sys::eval(
+        "(function() {
+            var render = function() {
+                var dark = document.documentElement.classList.contains('dark');
+                mermaid.initialize({ startOnLoad: false, theme: dark ? 'dark' : 'default' });
+                var list = document.querySelectorAll('.mermaid-diagram:not([data-mermaid])');
+                for (var i = 0; i < list.length; i++) {
+                    (function(el, id) {
+                        var source = el.textContent;
+                        el.setAttribute('data-mermaid', 'true');
+                        mermaid.render(id, source).then(function(result) {
+                            el.innerHTML = result.svg;
+                        }).catch(function(err) {
+                            console.warn('mermaid: ' + err);
+                            var leftover = document.getElementById('d' + id);
+                            if (leftover) { leftover.remove(); }
+                            var pre = document.createElement('pre');
+                            pre.className = 'text-left text-sm';
+                            pre.textContent = source;
+                            el.replaceChildren(pre);
+                        });
+                    })(list[i], 'mermaid-' + Date.now() + '-' + i);
+                }
+            };
+            if (window.mermaid) { render(); return; }
+            var el = document.head.querySelector('script[data-karaty=\"mermaid\"]');
+            if (el != null) { el.addEventListener('load', render); }
+        })();",
+    );
+}
@@ -1,3 +1,14 @@
+pub mod analytics;
 pub mod data;
+pub mod emoji;
+pub mod head;
 pub mod markdown;
+pub mod math;
+pub mod mermaid;
+pub mod offline;
+pub mod prefetch;
+pub mod print;
+pub mod sanitize;
+pub mod search;
+pub mod slug;
 pub mod template_loader;
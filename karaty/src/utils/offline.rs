@@ -0,0 +1,39 @@
+// offline support by service worker (`public/sw.js`), enabled by `site.offline`.
+
+const SERVICE_WORKER: &str = "/sw.js";
+
+/// register the service worker, it caches the app shell & every fetched content.
+/// `base_path` is the normalized `site.base-path`.
+pub fn register(base_path: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(src) {{
+            if (!('serviceWorker' in navigator)) {{ return; }}
+            navigator.serviceWorker.register(src).catch(function(err) {{
+                console.warn('service worker registration failed: ' + err);
+            }});
+        }})({});",
+        serde_json::to_string(&format!("{base_path}{SERVICE_WORKER}")).unwrap_or_default()
+    ));
+}
+
+/// remove the registered service worker & its caches, used when offline support is disabled.
+pub fn unregister(base_path: &str) {
+    let _ = js_sys::eval(&format!(
+        "(function(src) {{
+            if (!('serviceWorker' in navigator)) {{ return; }}
+            navigator.serviceWorker.getRegistrations().then(function(list) {{
+                list.forEach(function(reg) {{
+                    var worker = reg.active || reg.waiting || reg.installing;
+                    if (worker && new URL(worker.scriptURL).pathname == src) {{ reg.unregister(); }}
+                }});
+            }});
+            if (window.caches) {{
+                caches.keys().then(function(keys) {{
+                    keys.filter(function(key) {{ return key.startsWith('karaty-offline-'); }})
+                        .forEach(function(key) {{ caches.delete(key); }});
+                }});
+            }}
+        }})({});",
+        serde_json::to_string(&format!("{base_path}{SERVICE_WORKER}")).unwrap_or_default()
+    ));
+}
@@ -0,0 +1,86 @@
+// prefetch page content before navigation, fetched content is stored in the content cache.
+
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::config::{Config, RoutingInfo};
+
+use super::data::{load_page_file, MAX_NESTED_DEPTH};
+
+thread_local! {
+    /// files which have been prefetched (or are being prefetched).
+    static PREFETCHED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// find the file displayed by the access path, directory routes return `None`.
+pub fn page_file(config: &Config, routing: &[RoutingInfo], path: &str) -> Option<String> {
+    let path = path.trim_end_matches('/');
+    let path = if path.is_empty() { "/" } else { path };
+    if let Some(file) = config.file_map.get(path) {
+        return Some(file.trim_start_matches('/').to_string());
+    }
+    let segments = path.split('/').collect::<Vec<&str>>();
+    for info in routing {
+        let RoutingInfo::FileBind {
+            path: bind_path,
+            file,
+            ..
+        } = info
+        else {
+            continue;
+        };
+        let bind_path = bind_path.trim_end_matches('/');
+        let bind_path = if bind_path.is_empty() { "/" } else { bind_path };
+        let pattern = bind_path.split('/').collect::<Vec<&str>>();
+        if pattern.len() != segments.len() {
+            continue;
+        }
+        let mut values: Vec<(String, String)> = vec![];
+        let matched = pattern.iter().zip(&segments).all(|(p, s)| match p.strip_prefix(':') {
+            Some(name) => {
+                values.push((name.to_string(), s.to_string()));
+                !s.is_empty()
+            }
+            None => p == s,
+        });
+        if !matched {
+            continue;
+        }
+        // nested segments (`path-1`, `path-2` ...) are joined to their base segment
+        let mut file = file.clone();
+        for (name, value) in &values {
+            let mut value = value.clone();
+            for i in 1..MAX_NESTED_DEPTH {
+                match values.iter().find(|v| v.0 == format!("{name}-{i}")) {
+                    Some(nested) => value.push_str(&format!("/{}", nested.1)),
+                    None => break,
+                }
+            }
+            file = file.replace(&format!("{{{name}}}"), &value);
+        }
+        let file = file.trim_start_matches('/').to_string();
+        let has_extension = file
+            .rsplit('/')
+            .next()
+            .map(|v| v.contains('.'))
+            .unwrap_or(false);
+        return if has_extension && !file.contains('{') {
+            Some(file)
+        } else {
+            None
+        };
+    }
+    None
+}
+
+/// load the file into content cache, files which have been prefetched are skipped.
+pub async fn prefetch(config: &Config, file: &str, lang: Option<&str>) {
+    let key = format!("{}:{file}", lang.unwrap_or_default());
+    if !PREFETCHED.with(|v| v.borrow_mut().insert(key.clone())) {
+        return;
+    }
+    if let Err(e) = load_page_file(config, file, lang).await {
+        log::debug!("prefetch `{}` failed: {}", file, e);
+        // allow retry on next hover
+        PREFETCHED.with(|v| v.borrow_mut().remove(&key));
+    }
+}
@@ -0,0 +1,23 @@
+// print friendly styles, injected when `site.print-style` is enabled.
+
+use super::head;
+
+/// navbar, footer & floating buttons are hidden, colors are forced to black on white,
+/// and width limits are removed so the content uses the whole paper.
+const PRINT_STYLE: &str = "@media print {
+    nav, .karaty-footer, .karaty-no-print, .giscus { display: none !important; }
+    html, body, body * {
+        background: transparent !important;
+        color: #000 !important;
+        box-shadow: none !important;
+    }
+    [class*=\"max-w-\"], .prose { max-width: none !important; }
+    pre { white-space: pre-wrap !important; border: 1px solid #ddd; }
+    pre, img, table, blockquote { break-inside: avoid; }
+    h1, h2, h3, h4 { break-after: avoid; }
+}";
+
+/// add print styles into document head.
+pub fn load() {
+    head::add_style("karaty-print-style", PRINT_STYLE);
+}
@@ -0,0 +1,506 @@
+/// tags which can be kept in rendered html, cover all elements targeted by prose styles.
+/// `input` is only kept as disabled checkbox of task lists.
+const ALLOWED_TAGS: [&str; 57] = [
+    "a", "abbr", "article", "b", "blockquote", "br", "caption", "center", "code", "col",
+    "colgroup", "dd", "del", "details", "div", "dl", "dt", "em", "figcaption", "figure", "h1",
+    "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "input", "ins", "kbd", "li", "mark", "ol",
+    "p", "picture", "pre", "s", "section", "small", "source", "span", "strong", "sub",
+    "summary", "sup", "table", "tbody", "td", "tfoot", "th", "thead", "tr", "u", "ul", "video",
+];
+
+/// tags which will be removed with all their content.
+const DROPPED_TAGS: [&str; 12] = [
+    "script", "style", "iframe", "object", "embed", "noscript", "template", "textarea", "title",
+    "frameset", "frame", "applet",
+];
+
+/// embed tags, only allowed when embeds are enabled (`allow-html` flag).
+const EMBED_TAGS: [&str; 3] = ["iframe", "audio", "track"];
+
+const GLOBAL_ATTRIBUTES: [&str; 6] = ["class", "id", "title", "lang", "dir", "align"];
+
+/// attributes contain url, value will be checked by `is_safe_url`.
+const URL_ATTRIBUTES: [&str; 3] = ["href", "src", "poster"];
+
+fn allowed_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "a" => &["href", "target", "rel", "name"],
+        "img" => &["src", "alt", "width", "height", "loading", "decoding"],
+        "video" => &[
+            "src", "poster", "controls", "width", "height", "autoplay", "loop", "muted",
+            "playsinline",
+        ],
+        "source" => &["src", "type"],
+        "td" | "th" => &["colspan", "rowspan", "align"],
+        "ol" => &["start", "type", "reversed"],
+        "li" => &["value"],
+        "input" => &["type", "checked"],
+        "col" | "colgroup" => &["span"],
+        "details" => &["open"],
+        _ => &[],
+    }
+}
+
+fn embed_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "iframe" => &[
+            "src", "width", "height", "allow", "allowfullscreen", "frameborder", "loading",
+            "referrerpolicy",
+        ],
+        "audio" => &["src", "controls", "loop", "muted", "preload"],
+        "track" => &["src", "kind", "srclang", "label", "default"],
+        // inline style is allowed for custom layout
+        _ => &["style"],
+    }
+}
+
+/// decode character references like browsers do in attribute values, they may be used to hide
+/// url scheme (e.g. `&#106avascript:`). numeric references end at the first non-digit,
+/// with or without `;`, unknown named references are kept as is.
+fn decode_entities(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        match decode_reference(rest) {
+            Some((c, length)) => {
+                result.push(c);
+                rest = &rest[length..];
+            }
+            None => result.push('&'),
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// decode one reference following `&`, return the char and the byte length it takes.
+fn decode_reference(input: &str) -> Option<(char, usize)> {
+    if let Some(numeric) = input.strip_prefix('#') {
+        let (radix, start) = match numeric.as_bytes().first() {
+            Some(b'x' | b'X') => (16, 2),
+            _ => (10, 1),
+        };
+        let digits = input[start..]
+            .chars()
+            .take_while(|c| c.is_digit(radix))
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        let end = start + digits;
+        // out of range & null references are replaced, like browsers do
+        let c = u32::from_str_radix(&input[start..end], radix)
+            .ok()
+            .filter(|v| *v != 0)
+            .and_then(char::from_u32)
+            .unwrap_or('\u{fffd}');
+        let length = if input[end..].starts_with(';') {
+            end + 1
+        } else {
+            end
+        };
+        return Some((c, length));
+    }
+    let name = input
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+        .unwrap_or_default();
+    if !input[name.len()..].starts_with(';') {
+        return None;
+    }
+    let c = match name.to_lowercase().as_str() {
+        "colon" => ':',
+        "tab" => '\t',
+        "newline" => '\n',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" => '\'',
+        "lt" => '<',
+        "gt" => '>',
+        "sol" => '/',
+        "num" => '#',
+        "quest" => '?',
+        _ => return None,
+    };
+    Some((c, name.len() + 1))
+}
+
+/// only allow relative url & `http`, `https`, `mailto`, `tel` scheme, `data:image/` for images.
+pub fn is_safe_url(url: &str, allow_data_image: bool) -> bool {
+    is_safe_decoded_url(&decode_entities(url), allow_data_image)
+}
+
+/// same as `is_safe_url`, character references in `url` are already decoded.
+fn is_safe_decoded_url(url: &str, allow_data_image: bool) -> bool {
+    // browsers ignore whitespace & control characters in url scheme
+    let url = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    let scheme_end = url.find(':');
+    let path_start = url.find(['/', '?', '#']);
+    match (scheme_end, path_start) {
+        (Some(colon), Some(path)) if path < colon => true,
+        (Some(colon), _) => {
+            let scheme = &url[..colon];
+            matches!(scheme, "http" | "https" | "mailto" | "tel")
+                || (allow_data_image && url.starts_with("data:image/"))
+        }
+        (None, _) => true,
+    }
+}
+
+struct Tag<'a> {
+    name: String,
+    closing: bool,
+    attributes: Vec<(String, Option<&'a str>)>,
+    /// byte length of the whole tag
+    length: usize,
+}
+
+/// parse a tag start from `<`, return None if it is not a valid tag.
+fn parse_tag(input: &str) -> Option<Tag<'_>> {
+    let bytes = input.as_bytes();
+    let mut i = 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+    let name_start = i;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+        i += 1;
+    }
+    if i == name_start || !bytes[name_start].is_ascii_alphabetic() {
+        return None;
+    }
+    let name = input[name_start..i].to_lowercase();
+
+    let mut attributes = vec![];
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return None;
+        }
+        if bytes[i] == b'>' {
+            i += 1;
+            break;
+        }
+        let attr_start = i;
+        while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+        {
+            i += 1;
+        }
+        let attr_name = input[attr_start..i].to_lowercase();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let mut value = None;
+        if bytes.get(i) == Some(&b'=') {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            match bytes.get(i) {
+                Some(quote @ (b'"' | b'\'')) => {
+                    let end = input[i + 1..].find(*quote as char)? + i + 1;
+                    value = Some(&input[i + 1..end]);
+                    i = end + 1;
+                }
+                Some(_) => {
+                    let start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    value = Some(&input[start..i]);
+                }
+                None => return None,
+            }
+        }
+        if !attr_name.is_empty() {
+            attributes.push((attr_name, value));
+        }
+    }
+    Some(Tag {
+        name,
+        closing,
+        attributes,
+        length: i,
+    })
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn render_tag(tag: &Tag, embeds: bool, resolve: &dyn Fn(&str) -> Option<String>) -> String {
+    if tag.closing {
+        return format!("</{}>", tag.name);
+    }
+    // browsers use the first `type` attribute
+    if tag.name == "input" {
+        let kind = tag.attributes.iter().find(|(name, _)| name == "type");
+        if !matches!(kind, Some((_, Some(v))) if v.eq_ignore_ascii_case("checkbox")) {
+            return String::new();
+        }
+    }
+    let mut result = format!("<{}", tag.name);
+    let mut has_target = false;
+    for (name, value) in &tag.attributes {
+        let name = name.as_str();
+        let allowed = GLOBAL_ATTRIBUTES.contains(&name)
+            || allowed_attributes(&tag.name).contains(&name)
+            || (embeds
+                && (embed_attributes(&tag.name).contains(&name)
+                    || embed_attributes("").contains(&name)));
+        if !allowed {
+            continue;
+        }
+        if name == "rel" {
+            continue;
+        }
+        if name == "target" {
+            has_target = true;
+        }
+        // url is checked after decoding, and written back with `&` escaped,
+        // so the browser reads exactly the checked url
+        if URL_ATTRIBUTES.contains(&name) {
+            let url = decode_entities(value.unwrap_or_default());
+            let allow_data_image = tag.name == "img" && name == "src";
+            if !is_safe_decoded_url(&url, allow_data_image) {
+                continue;
+            }
+            let url = resolve(&url).unwrap_or(url);
+            let url = escape_attribute(&url.replace('&', "&amp;"));
+            result.push_str(&format!(" {}=\"{}\"", name, url));
+            continue;
+        }
+        match *value {
+            Some(value) => result.push_str(&format!(" {}=\"{}\"", name, escape_attribute(value))),
+            None => result.push_str(&format!(" {}", name)),
+        }
+    }
+    if has_target {
+        result.push_str(" rel=\"noopener noreferrer\"");
+    }
+    if tag.name == "input" {
+        result.push_str(" disabled");
+    }
+    // images are loaded lazily unless the content sets its own value
+    if tag.name == "img" {
+        for (attr, value) in IMAGE_ATTRIBUTES {
+            if !tag.attributes.iter().any(|(name, _)| *name == attr) {
+                result.push_str(&format!(" {}=\"{}\"", attr, value));
+            }
+        }
+    }
+    result.push('>');
+    result
+}
+
+/// attributes added to every image (markdown `![]()` & raw `<img>`), images are loaded lazily.
+pub const IMAGE_ATTRIBUTES: [(&str, &str); 2] = [("loading", "lazy"), ("decoding", "async")];
+
+/// value of a default image attribute, see [`IMAGE_ATTRIBUTES`].
+pub fn image_attribute(name: &str) -> Option<&'static str> {
+    IMAGE_ATTRIBUTES
+        .iter()
+        .find(|(attr, _)| *attr == name)
+        .map(|(_, value)| *value)
+}
+
+/// sanitize html with an allowlist, scripts, event handlers & unsafe urls will be removed.
+/// urls in `href` & `src` will be replaced if `resolve` return a new one.
+/// `embeds` allows `iframe`, `audio` & inline style, scripts are still removed.
+pub fn sanitize_html_with(
+    html: &str,
+    embeds: bool,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> String {
+    let mut result = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            rest = match rest.find("-->") {
+                Some(end) => &rest[end + 3..],
+                None => "",
+            };
+            continue;
+        }
+
+        let tag = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                result.push_str("&lt;");
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag.length..];
+
+        let embedded = embeds && EMBED_TAGS.contains(&tag.name.as_str());
+        if DROPPED_TAGS.contains(&tag.name.as_str()) && !embedded {
+            if !tag.closing {
+                // skip all content until the close tag
+                let close = format!("</{}", tag.name);
+                rest = match rest.to_ascii_lowercase().find(&close) {
+                    Some(end) => {
+                        let after = &rest[end..];
+                        match after.find('>') {
+                            Some(gt) => &after[gt + 1..],
+                            None => "",
+                        }
+                    }
+                    None => "",
+                };
+            }
+            continue;
+        }
+
+        if ALLOWED_TAGS.contains(&tag.name.as_str()) || embedded {
+            result.push_str(&render_tag(&tag, embeds, resolve));
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitize(html: &str) -> String {
+        sanitize_html_with(html, false, &|_| None)
+    }
+
+    #[test]
+    fn entity_encoded_scheme_is_removed() {
+        for href in [
+            "&#106avascript:alert(1)",
+            "&#x6Aavascript:alert(1)",
+            "&#106;avascript:alert(1)",
+            "&#0000106avascript:alert(1)",
+            "javascript&colon;alert(1)",
+            "&#x6a;&#x61;&#x76;&#x61;script&#58;alert(1)",
+        ] {
+            let html = sanitize(&format!("<a href=\"{href}\">x</a>"));
+            assert_eq!(html, "<a>x</a>", "{href}");
+        }
+    }
+
+    #[test]
+    fn whitespace_in_scheme_is_removed() {
+        for href in [
+            " javascript:alert(1)",
+            "java\tscript:alert(1)",
+            "java&Tab;script:alert(1)",
+            "java&#x09;script:alert(1)",
+            "java&NewLine;script:alert(1)",
+            "\u{1}javascript:alert(1)",
+        ] {
+            let html = sanitize(&format!("<a href=\"{href}\">x</a>"));
+            assert_eq!(html, "<a>x</a>", "{href:?}");
+        }
+    }
+
+    #[test]
+    fn scheme_case_is_ignored() {
+        assert_eq!(
+            sanitize("<a href=\"JaVaScRiPt:alert(1)\">x</a>"),
+            "<a>x</a>"
+        );
+        assert_eq!(sanitize("<a href='VBSCRIPT:x'>x</a>"), "<a>x</a>");
+    }
+
+    #[test]
+    fn safe_urls_are_kept() {
+        assert_eq!(
+            sanitize("<a href=\"https://example.com/?a=1&amp;b=2\">x</a>"),
+            "<a href=\"https://example.com/?a=1&amp;b=2\">x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"#intro\">x</a>"),
+            "<a href=\"#intro\">x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"./a:b\">x</a>"),
+            "<a href=\"./a:b\">x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"mailto:me@example.com\">x</a>"),
+            "<a href=\"mailto:me@example.com\">x</a>"
+        );
+    }
+
+    #[test]
+    fn unknown_references_are_escaped() {
+        // the browser must not decode what the sanitizer didn't check
+        assert_eq!(
+            sanitize("<a href=\"/a?x=1&lpar;\">x</a>"),
+            "<a href=\"/a?x=1&amp;lpar;\">x</a>"
+        );
+    }
+
+    #[test]
+    fn data_url_only_for_images() {
+        let png = "data:image/png;base64,AAAA";
+        assert_eq!(sanitize(&format!("<a href=\"{png}\">x</a>")), "<a>x</a>");
+        assert!(sanitize(&format!("<img src=\"{png}\">")).contains(png));
+        assert_eq!(
+            sanitize("<img src=\"data:text/html,x\">"),
+            "<img loading=\"lazy\" decoding=\"async\">"
+        );
+    }
+
+    #[test]
+    fn scripts_and_handlers_are_removed() {
+        assert_eq!(sanitize("a<script>alert(1)</script>b"), "ab");
+        assert_eq!(sanitize("<SCRIPT>alert(1)</SCRIPT>"), "");
+        assert_eq!(sanitize("<p onclick=\"alert(1)\">x</p>"), "<p>x</p>");
+        assert_eq!(sanitize("<svg onload=alert(1)>"), "");
+    }
+
+    #[test]
+    fn task_list_checkbox() {
+        assert_eq!(
+            sanitize("<input type=\"checkbox\" checked=\"\" disabled=\"\" />"),
+            "<input type=\"checkbox\" checked=\"\" disabled>"
+        );
+        assert_eq!(sanitize("<input type=\"text\" value=\"x\">"), "");
+        assert_eq!(sanitize("<input type=\"text\" type=\"checkbox\">"), "");
+    }
+
+    #[test]
+    fn image_attributes() {
+        assert_eq!(image_attribute("loading"), Some("lazy"));
+        assert_eq!(image_attribute("decoding"), Some("async"));
+        assert_eq!(image_attribute("src"), None);
+    }
+
+    #[test]
+    fn images_are_lazy_loaded() {
+        assert_eq!(
+            sanitize("<img src=\"a.png\" alt=\"a\">"),
+            "<img src=\"a.png\" alt=\"a\" loading=\"lazy\" decoding=\"async\">"
+        );
+        // the content's own values are kept
+        assert_eq!(
+            sanitize("<img src=\"a.png\" loading=\"eager\">"),
+            "<img src=\"a.png\" loading=\"eager\" decoding=\"async\">"
+        );
+        let html = crate::utils::markdown::parse_markdown("![logo](a.png)", false).unwrap();
+        assert!(html.contains("loading=\"lazy\""), "{html}");
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use karaty_blueprint::TemplateData;
+use regex::Regex;
+
+use crate::config::RoutingInfo;
+
+use super::markdown::plain_text;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchPage {
+    pub name: String,
+    pub title: String,
+    pub link: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub title: String,
+    pub link: String,
+    pub excerpt: String,
+    pub score: usize,
+}
+
+/// in-memory full-text index for all routable pages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchIndex {
+    pub pages: Vec<SearchPage>,
+    /// normalized token -> page names
+    pub tokens: HashMap<String, Vec<String>>,
+    /// page name -> cached excerpt
+    pub excerpts: HashMap<String, String>,
+}
+
+/// split text to lowercase alphanumeric tokens.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+        .collect()
+}
+
+/// flatten loaded data to a `file path -> content` map.
+pub fn flatten_pages(
+    data: &HashMap<String, TemplateData>,
+    prefix: &str,
+    result: &mut HashMap<String, String>,
+) {
+    for (name, value) in data {
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}/{name}")
+        };
+        match value {
+            TemplateData::File(content) => {
+                result.insert(path, content.clone());
+            }
+            TemplateData::Directory(dir) => flatten_pages(dir, &path, result),
+        }
+    }
+}
+
+/// find the route path which display the file.
+pub fn page_link(routing: &[RoutingInfo], file: &str) -> Option<String> {
+    let segment_re = Regex::new(r"\\\{([^}]*)\\\}").unwrap();
+    for info in routing {
+        if let RoutingInfo::FileBind {
+            path, file: bind, config, ..
+        } = info
+        {
+            let bind = bind.trim_start_matches('/');
+            if bind.is_empty() {
+                continue;
+            }
+            if bind == file && !path.contains(':') {
+                return Some(path.clone());
+            }
+            let nested = config
+                .as_ref()
+                .and_then(|v| v.get("nested"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if bind.contains('{') {
+                let escaped = regex::escape(bind);
+                let replacement = if nested {
+                    "(?P<${1}>.+)"
+                } else {
+                    "(?P<${1}>[^/]+)"
+                };
+                let pattern = segment_re.replace_all(&escaped, replacement);
+                if let Ok(re) = Regex::new(&format!("^{pattern}$")) {
+                    if let Some(caps) = re.captures(file) {
+                        let mut link = path.clone();
+                        for name in re.capture_names().flatten() {
+                            if let Some(value) = caps.name(name) {
+                                link = link.replace(&format!(":{name}"), value.as_str());
+                            }
+                        }
+                        return Some(link);
+                    }
+                }
+            }
+            let segment = config
+                .as_ref()
+                .and_then(|v| v.get("file-segment"))
+                .and_then(|v| v.as_str());
+            let rest = file.strip_prefix(&format!("{bind}/"));
+            if let (Some(segment), Some(rest)) = (segment, rest) {
+                if !rest.contains('/') {
+                    let name = rest.trim_end_matches(".md");
+                    return Some(path.replace(&format!(":{segment}"), name));
+                }
+            }
+        }
+    }
+    None
+}
+
+impl SearchIndex {
+    pub fn build(pages: &HashMap<String, String>, routing: &[RoutingInfo]) -> Self {
+        let tag_re = Regex::new(r"<[^>]*>").unwrap();
+        let mut result = vec![];
+        for (name, content) in pages {
+            let file_name = name.split('/').next_back().unwrap_or_default();
+            if file_name.starts_with('_') {
+                continue;
+            }
+            let (title, text) = if name.ends_with(".md") {
+                plain_text(content)
+            } else if name.ends_with(".html") {
+                let text = tag_re.replace_all(content, " ");
+                (None, text.split_whitespace().collect::<Vec<&str>>().join(" "))
+            } else {
+                continue;
+            };
+            if let Some(link) = page_link(routing, name) {
+                let title = title.unwrap_or_else(|| {
+                    file_name.split('.').next().unwrap_or_default().to_string()
+                });
+                result.push(SearchPage {
+                    name: name.clone(),
+                    title,
+                    link,
+                    text,
+                });
+            }
+        }
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut tokens: HashMap<String, Vec<String>> = HashMap::new();
+        let mut excerpts = HashMap::new();
+        for page in &result {
+            let text = format!("{} {}", page.title, page.text);
+            for token in tokenize(&text) {
+                let list = tokens.entry(token).or_default();
+                if list.last() != Some(&page.name) {
+                    list.push(page.name.clone());
+                }
+            }
+            excerpts.insert(page.name.clone(), page.text.chars().take(160).collect());
+        }
+
+        Self {
+            pages: result,
+            tokens,
+            excerpts,
+        }
+    }
+
+    /// page names which contain all query tokens, the last token is used as prefix.
+    pub fn lookup(&self, query: &str) -> Vec<String> {
+        let terms = tokenize(query);
+        let mut result: Option<Vec<String>> = None;
+        for (i, term) in terms.iter().enumerate() {
+            let mut names = vec![];
+            if i == terms.len() - 1 {
+                for (token, list) in &self.tokens {
+                    if token.starts_with(term.as_str()) {
+                        names.extend(list.iter().cloned());
+                    }
+                }
+            } else if let Some(list) = self.tokens.get(term) {
+                names.extend(list.iter().cloned());
+            }
+            result = Some(match result {
+                Some(prev) => prev.into_iter().filter(|v| names.contains(v)).collect(),
+                None => names,
+            });
+        }
+        let mut result = result.unwrap_or_default();
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    /// naive search over `lookup` result, rank by term frequency.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let terms = tokenize(query);
+        let names = self.lookup(query);
+        let mut result = vec![];
+        for page in self.pages.iter().filter(|v| names.contains(&v.name)) {
+            let title = page.title.to_lowercase();
+            let text = page.text.to_lowercase();
+            let mut score = 0;
+            for term in &terms {
+                score += title.matches(term.as_str()).count() * 5
+                    + text.matches(term.as_str()).count();
+            }
+            let excerpt = match terms.first() {
+                Some(term) if text.contains(term.as_str()) => excerpt(&page.text, &text, term),
+                _ => self.excerpts.get(&page.name).cloned().unwrap_or_default(),
+            };
+            result.push(SearchResult {
+                title: page.title.clone(),
+                link: page.link.clone(),
+                excerpt,
+                score,
+            });
+        }
+        result.sort_by_key(|b| std::cmp::Reverse(b.score));
+        result.truncate(limit);
+        result
+    }
+}
+
+/// text around the first match of term.
+fn excerpt(text: &str, lower: &str, term: &str) -> String {
+    let start = lower
+        .find(term)
+        .map(|pos| lower[..pos].chars().count())
+        .unwrap_or(0);
+    let from = start.saturating_sub(40);
+    let excerpt = text.chars().skip(from).take(120).collect::<String>();
+    if from > 0 {
+        format!("...{}", excerpt.trim())
+    } else {
+        excerpt.trim().to_string()
+    }
+}
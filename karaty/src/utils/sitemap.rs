@@ -0,0 +1,114 @@
+//! Sitemap generator, this module is used by `build.rs`.
+//!
+//! crawlers can't run the client-side app, so `sitemap.xml` is generated as a static file at build time.
+
+use std::{fs, path::Path, path::PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SitemapEntry {
+    /// access path, e.g. `/blog/hello`
+    pub path: String,
+    /// `YYYY-MM-DD` format date
+    pub lastmod: Option<String>,
+}
+
+/// expand a routing path to access paths by files in content directory, return (path, file).
+///
+/// static path is used directly, one dynamic segment (e.g. `/blog/:path`) is expanded by
+/// the files matched by `{path}` in `file` (e.g. `posts/blog/{path}.md`),
+/// or the markdown files in `file` directory when the segment is `file-segment` (e.g. `docs` template).
+pub fn expand_route(
+    path: &str,
+    file: Option<&str>,
+    file_segment: Option<&str>,
+    content: &Path,
+) -> Vec<(String, Option<PathBuf>)> {
+    let dynamic = path
+        .split('/')
+        .filter(|v| v.starts_with(':'))
+        .collect::<Vec<&str>>();
+    if dynamic.is_empty() {
+        let file = file
+            .filter(|v| Path::new(v).extension().is_some())
+            .map(|v| content.join(v));
+        return vec![(path.to_string(), file)];
+    }
+    let (Some(file), [segment]) = (file, dynamic.as_slice()) else {
+        return vec![];
+    };
+
+    let sign = format!("{{{}}}", &segment[1..]);
+    let (dir, pattern) = if file.contains(&sign) {
+        match file.rsplit_once('/') {
+            Some((dir, name)) if !dir.contains(&sign) => (dir.to_string(), name.to_string()),
+            None => (String::new(), file.to_string()),
+            _ => return vec![],
+        }
+    } else if file_segment == Some(&segment[1..]) && Path::new(file).extension().is_none() {
+        (file.to_string(), format!("{sign}.md"))
+    } else {
+        return vec![];
+    };
+    let Some((prefix, suffix)) = pattern.split_once(&sign) else {
+        return vec![];
+    };
+
+    let dir = content.join(dir);
+    let mut result = vec![];
+    for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !entry.path().is_file() || name.len() <= prefix.len() + suffix.len() {
+            continue;
+        }
+        let Some(value) = name
+            .strip_prefix(prefix)
+            .and_then(|v| v.strip_suffix(suffix))
+        else {
+            continue;
+        };
+        // `_index.md` & other underscore files are not pages
+        if value.starts_with('_') || value.starts_with('.') {
+            continue;
+        }
+        result.push((path.replace(*segment, value), Some(entry.path())));
+    }
+    result
+}
+
+/// `YYYY-MM-DD` part of front matter date, invalid date will be ignored.
+pub fn lastmod(date: &str) -> Option<String> {
+    let date = date.trim().get(0..10)?;
+    let valid = date.char_indices().all(|(index, c)| match index {
+        4 | 7 => c == '-',
+        _ => c.is_ascii_digit(),
+    });
+    valid.then(|| date.to_string())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn sitemap(site_url: &str, entries: &[SitemapEntry]) -> String {
+    let site_url = site_url.trim_end_matches('/');
+    let mut result = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    result.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for entry in entries {
+        result.push_str("<url>\n");
+        result.push_str(&format!(
+            "<loc>{}{}</loc>\n",
+            escape(site_url),
+            escape(&entry.path)
+        ));
+        if let Some(date) = &entry.lastmod {
+            result.push_str(&format!("<lastmod>{date}</lastmod>\n"));
+        }
+        result.push_str("</url>\n");
+    }
+    result.push_str("</urlset>\n");
+    result
+}
@@ -0,0 +1,76 @@
+/// latin letters with diacritics and their ascii form.
+const TRANSLITERATION: [(&str, &str); 13] = [
+    ("àáâãäåāă", "a"),
+    ("çćč", "c"),
+    ("ďđ", "d"),
+    ("èéêëēėęě", "e"),
+    ("ìíîïīį", "i"),
+    ("ñńň", "n"),
+    ("òóôõöøō", "o"),
+    ("ŕř", "r"),
+    ("śšş", "s"),
+    ("ťţ", "t"),
+    ("ùúûüūůű", "u"),
+    ("ýÿ", "y"),
+    ("źżž", "z"),
+];
+
+fn transliterate(c: char) -> Option<&'static str> {
+    match c {
+        'ß' => Some("ss"),
+        'æ' => Some("ae"),
+        'œ' => Some("oe"),
+        _ => TRANSLITERATION
+            .iter()
+            .find(|(list, _)| list.contains(c))
+            .map(|(_, ascii)| *ascii),
+    }
+}
+
+/// url & anchor safe slug: `Hello, Wörld!` -> `hello-world`.
+///
+/// letters are lowercased and common latin diacritics are transliterated,
+/// other unicode letters & digits (e.g. CJK) are kept as is,
+/// whitespace, `-` & `_` become a single `-`, other characters are removed.
+pub fn slugify(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        if let Some(ascii) = transliterate(c) {
+            result.push_str(ascii);
+        } else if c.is_alphanumeric() {
+            result.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !result.ends_with('-') {
+            result.push('-');
+        }
+    }
+    result.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_punctuation_and_separators() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  foo -- bar__baz  "), "foo-bar-baz");
+        assert_eq!(slugify("-Edge- Case-"), "edge-case");
+        assert_eq!(slugify("What's new in v2.0?"), "whats-new-in-v20");
+    }
+
+    #[test]
+    fn diacritics_are_transliterated() {
+        assert_eq!(slugify("Hello, Wörld!"), "hello-world");
+        assert_eq!(
+            slugify("Ærø Straße Crème Brûlée"),
+            "aero-strasse-creme-brulee"
+        );
+    }
+
+    #[test]
+    fn unicode_letters_are_kept() {
+        assert_eq!(slugify("快速 开始"), "快速-开始");
+        assert_eq!(slugify("Привет мир"), "привет-мир");
+        assert_eq!(slugify("!!!"), "");
+    }
+}
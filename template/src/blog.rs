@@ -1,9 +1,16 @@
 use std::collections::HashMap;
 
 use dioxus::prelude::*;
+use karaty_blueprint::front_matter::{meta_string, parse_front_matter};
 use karaty_blueprint::Value;
 use karaty_blueprint::{TemplateData, TemplateDataType, TemplateProps, Templates};
 
+use crate::utils::{
+    date::{format_date, parse_date},
+    json_ld::{self, JsonLdScript},
+    markdown::excerpt,
+};
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct PostInfo {
     pub title: String,
@@ -12,6 +19,8 @@ pub struct PostInfo {
     pub date: String,
     pub path: String,
     pub content: String,
+    pub excerpt: String,
+    pub author: Option<String>,
     pub sub_group: Vec<String>,
 }
 
@@ -28,29 +37,20 @@ pub fn BlogListPreset(cx: Scope<TemplateProps>) -> Element {
             .unwrap_or(&cx.props.route.bound_path)
             .to_string();
         let site_title = cx.props.utility.app_config.site.name.clone();
+        let tag_link = tag_link(&cx.props.config);
+        let date_format = cx.props.utility.app_config.site.date_format.clone();
         let v = to_info(data.clone());
         let v = sort_by_date(v);
         let list = v.iter().map(|v| {
-            let category = v.category.clone().unwrap_or("Default".to_string());
-            let tags = v.tags.iter().map(|tag| {
-                rsx! {
-                    span { class: "text-xs mr-1 inline-block py-1 px-2.5 \
-                    leading-none text-center whitespace-nowrap align-baseline \
-                    font-bold bg-gray-700 text-white rounded",
-                        "{tag}"
-                    }
-                }
-            });
             let link = format!("{link}/{}", &v.path);
+            let tag_link = tag_link.clone();
+            let date_format = date_format.clone();
             rsx! {
-                dioxus_retrouter::Link { to: "{link}",
-                    h1 { class: "text-3xl font-bold text-gray-500 hover:text-gray-900 \
-                    dark:text-gray-100 dark:hover:text-white",
-                        "{v.title}"
-                    }
-                    p { class: "text-gray-400 dark:text-gray-100", "{v.date} & {category}" }
-                    p { class: "mt-2", tags }
-                    hr { class: "mt-2 mb-4" }
+                PostSummary {
+                    info: v.clone(),
+                    link: link,
+                    tag_link: tag_link,
+                    date_format: date_format,
                 }
             }
         });
@@ -92,22 +92,30 @@ pub fn BlogContentPreset(cx: Scope<TemplateProps>) -> Element {
     temp.insert("self".to_string(), data.clone());
     let info = to_info(temp);
 
+    // structured data for search engines, updated when post changed
+    let json_ld = info.first().map(json_ld::blog_posting);
+    let json_ld_script = use_ref(cx, || None::<JsonLdScript>);
+    use_effect(cx, (&json_ld,), |(json_ld,)| {
+        to_owned![json_ld_script];
+        async move {
+            // the old script must be removed before the new one is inserted
+            json_ld_script.set(None);
+            json_ld_script.set(json_ld.and_then(|v| JsonLdScript::set(&v)));
+        }
+    });
+
     match info.get(0) {
         Some(info) => {
             let content = info.content.clone();
 
             let category = info.category.clone().unwrap_or("Default".to_string());
 
-            let tags = info.tags.iter().map(|tag| {
-                rsx! {
-                    span {
-                        class: "text-xs mr-1 inline-block py-1 px-2.5 \
-                            leading-none text-center whitespace-nowrap align-baseline \
-                            font-bold bg-gray-700 text-white rounded",
-                        "{tag}"
-                    }
-                }
-            });
+            let tags = info.tags.clone();
+            let tag_link = tag_link(&cx.props.config);
+            let date = format_date(
+                &info.date,
+                cx.props.utility.app_config.site.date_format.as_deref(),
+            );
 
             cx.render(rsx! {
                 section { class: "bg-cover bg-white dark:bg-gray-900 dark:text-white",
@@ -117,7 +125,7 @@ pub fn BlogContentPreset(cx: Scope<TemplateProps>) -> Element {
                             h1 { class: "text-4xl font-bold text-gray-600 dark:text-white",
                                 "{info.title}"
                             }
-                            p { class: "mt-1 text-gray-400 dark:text-gray-200", "{info.date} & {category}" }
+                            p { class: "mt-1 text-gray-400 dark:text-gray-200", "{date} & {category}" }
                             hr { class: "mt-2" }
                             div {
                                 class: "prose mt-4 dark:text-white dark:prose-invert",
@@ -127,7 +135,9 @@ pub fn BlogContentPreset(cx: Scope<TemplateProps>) -> Element {
                                 }
                             }
                             hr { class: "mt-4" }
-                            p { class: "mt-4", tags }
+                            p { class: "mt-4",
+                                TagList { tags: tags, link: tag_link }
+                            }
                             Giscus {}
                             div { class: "giscus flex justify-center container mx-auto my-12" }
                             Footer {}
@@ -145,67 +155,206 @@ pub fn BlogContentPreset(cx: Scope<TemplateProps>) -> Element {
     }
 }
 
+#[derive(Props, PartialEq)]
+pub struct PostSummaryProps {
+    info: PostInfo,
+    link: String,
+    #[props(!optional, default)]
+    tag_link: Option<String>,
+    #[props(!optional, default)]
+    date_format: Option<String>,
+}
+
+/// post item in blog list: title, date, category, excerpt and tags.
+#[allow(non_snake_case)]
+pub fn PostSummary(cx: Scope<PostSummaryProps>) -> Element {
+    let info = &cx.props.info;
+    let link = crate::site_link(cx, &cx.props.link);
+    let category = info.category.clone().unwrap_or("Default".to_string());
+    let date = format_date(&info.date, cx.props.date_format.as_deref());
+    cx.render(rsx! {
+        dioxus_retrouter::Link { to: "{link}",
+            h1 { class: "text-3xl font-bold text-gray-500 hover:text-gray-900 \
+            dark:text-gray-100 dark:hover:text-white",
+                "{info.title}"
+            }
+            p { class: "text-gray-400 dark:text-gray-100", "{date} & {category}" }
+            if !info.excerpt.is_empty() {
+                rsx! {
+                    p { class: "mt-2 text-gray-600 dark:text-gray-300", "{info.excerpt}" }
+                }
+            }
+        }
+        p { class: "mt-2",
+            TagList { tags: info.tags.clone(), link: cx.props.tag_link.clone() }
+        }
+        hr { class: "mt-2 mb-4" }
+    })
+}
+
+#[derive(Props, PartialEq)]
+pub struct TagListProps {
+    tags: Vec<String>,
+    /// tag page prefix, tags are clickable when it is set
+    #[props(!optional)]
+    link: Option<String>,
+}
+
+/// tag chips, link to `{link}/{tag}` page.
+#[allow(non_snake_case)]
+pub fn TagList(cx: Scope<TagListProps>) -> Element {
+    let class = "text-xs mr-1 inline-block py-1 px-2.5 \
+        leading-none text-center whitespace-nowrap align-baseline \
+        font-bold bg-gray-700 text-white rounded";
+    let tags = cx.props.tags.iter().map(|tag| match &cx.props.link {
+        Some(link) => {
+            let link = format!("{}/{}", link.trim_end_matches('/'), tag_slug(tag));
+            let link = crate::site_link(cx, &link);
+            rsx! {
+                dioxus_retrouter::Link { class: "{class} hover:bg-gray-900", to: "{link}", "{tag}" }
+            }
+        }
+        None => rsx! {
+            span { class: "{class}", "{tag}" }
+        },
+    });
+    cx.render(rsx! { tags })
+}
+
+/// tag name in url: `Web Dev` -> `web-dev`
+pub fn tag_slug(tag: &str) -> String {
+    tag.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// tag page prefix from `tag-link` config.
+pub fn tag_link(config: &HashMap<String, Value>) -> Option<String> {
+    config
+        .get("tag-link")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+/// posts with the tag (bound by `tag-segment`), use the same config as `blog::list`.
+#[allow(non_snake_case)]
+pub fn BlogTagPreset(cx: Scope<TemplateProps>) -> Element {
+    let TemplateData::Directory(data) = &cx.props.data else {
+        let display_error = cx.props.utility.error;
+        return cx.render(rsx! {
+            display_error {
+                title: format!("Unrecognized data type"),
+                content: format!("blog::tag template must load by Directory data-type")
+            }
+        });
+    };
+    let segment = cx
+        .props
+        .config
+        .get("tag-segment")
+        .and_then(|v| v.as_str())
+        .unwrap_or("tag");
+    let tag = cx
+        .props
+        .route
+        .segments
+        .get(segment)
+        .cloned()
+        .unwrap_or_default();
+    let link = cx
+        .props
+        .config
+        .get("content-link")
+        .and_then(|v| v.as_str())
+        .unwrap_or("/blog")
+        .to_string();
+    let tag_link = tag_link(&cx.props.config);
+    let date_format = cx.props.utility.app_config.site.date_format.clone();
+
+    let posts = to_info(data.clone())
+        .into_iter()
+        .filter(|v| v.tags.iter().any(|t| tag_slug(t) == tag_slug(&tag)))
+        .collect::<Vec<PostInfo>>();
+    let title = posts
+        .first()
+        .and_then(|v| v.tags.iter().find(|t| tag_slug(t) == tag_slug(&tag)))
+        .cloned()
+        .unwrap_or(tag);
+    let posts = sort_by_date(posts);
+    let empty = posts.is_empty();
+    let list = posts.iter().map(|v| {
+        let link = format!("{link}/{}", &v.path);
+        let tag_link = tag_link.clone();
+        let date_format = date_format.clone();
+        rsx! {
+            PostSummary {
+                info: v.clone(),
+                link: link,
+                tag_link: tag_link,
+                date_format: date_format,
+            }
+        }
+    });
+
+    let Navbar = cx.props.utility.navbar;
+    let Footer = cx.props.utility.footer;
+    cx.render(rsx! {
+        section { class: "bg-cover bg-white dark:bg-gray-900 dark:text-white",
+            Navbar {}
+            div { class: "flex h-full w-full items-center justify-center px-8",
+                div { class: "max-w-5xl text-center w-[60%]",
+                    h1 { class: "text-xl font-bold", "# {title}" }
+                    div { class: "mt-6",
+                        if empty {
+                            rsx! { p { class: "text-gray-500", "No posts found." } }
+                        }
+                        list
+                    }
+                    Footer {}
+                }
+            }
+        }
+    })
+}
+
 fn to_info(data: HashMap<String, TemplateData>) -> Vec<PostInfo> {
     let mut result = vec![];
     for (file_name, data) in data {
         if let TemplateData::File(meta_info) = data {
-            let mut type_mark = HashMap::new();
-
-            type_mark.insert("title".into(), "string");
-            type_mark.insert("tags".into(), "array");
-            type_mark.insert("category".into(), "string");
-            type_mark.insert("date".into(), "string");
-            type_mark.insert("released".into(), "bool");
-
-            let temp = markdown_meta_parser::MetaData {
-                content: meta_info,
-                required: vec!["title".to_string()],
-                type_mark,
+            if !file_name.ends_with(".md") && file_name != "self" {
+                continue;
             }
-            .parse()
-            .ok();
 
-            if temp.is_none() {
+            let Some((meta_info, content)) = parse_front_matter(&meta_info) else {
                 continue;
-            }
-            let (meta_info, content) = temp.unwrap();
-
-            if meta_info.get("released").is_some()
-                && meta_info
-                    .get("released")
-                    .unwrap()
-                    .clone()
-                    .as_bool()
-                    .unwrap()
-                    == false
-            {
+            };
+
+            if let Some(Value::Boolean(false)) = meta_info.get("released") {
                 continue;
             }
 
-            let title = meta_info.get("title").unwrap().clone();
-
-            let date = meta_info.get("date");
-            let date = if let Some(d) = date {
-                d.clone().as_string().unwrap()
-            } else {
-                "".to_string()
+            let Some(title) = meta_string(&meta_info, "title") else {
+                continue;
             };
 
-            let tags = meta_info.get("tags");
-            let tags = if let Some(v) = tags {
-                v.clone().as_array().unwrap()
-            } else {
-                vec![]
-            };
+            let date = meta_string(&meta_info, "date").unwrap_or_default();
 
-            let category = meta_info.get("category");
-            let category = if let Some(v) = category {
-                v.clone().as_string()
-            } else {
-                None
+            let tags = match meta_info.get("tags") {
+                Some(Value::Array(list)) => list
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|v| v.to_string()))
+                    .collect(),
+                _ => vec![],
             };
 
-            let title = title.as_string().unwrap();
+            let category = meta_string(&meta_info, "category");
+            let author = meta_string(&meta_info, "author");
+
+            let excerpt = meta_string(&meta_info, "excerpt")
+                .or(meta_string(&meta_info, "description"))
+                .unwrap_or_else(|| excerpt(&content, 160));
 
             let path = file_name.split(".").collect::<Vec<&str>>();
             let path = path[0..path.len() - 1].to_vec();
@@ -218,6 +367,8 @@ fn to_info(data: HashMap<String, TemplateData>) -> Vec<PostInfo> {
                 date,
                 path: path.clone(),
                 content,
+                excerpt,
+                author,
                 sub_group: Default::default(),
             };
             result.push(blog_info);
@@ -230,10 +381,10 @@ fn to_info(data: HashMap<String, TemplateData>) -> Vec<PostInfo> {
 
 fn sort_by_date(mut data: Vec<PostInfo>) -> Vec<PostInfo> {
     data.sort_by(|a, b| {
-        let a_date = chrono::NaiveDate::parse_from_str(&a.date, "%Y-%m-%d");
-        let b_date = chrono::NaiveDate::parse_from_str(&b.date, "%Y-%m-%d");
-        if a_date.is_ok() && b_date.is_ok() {
-            return b_date.unwrap().cmp(&a_date.unwrap());
+        let a_date = parse_date(&a.date);
+        let b_date = parse_date(&b.date);
+        if let (Some(a_date), Some(b_date)) = (a_date, b_date) {
+            return b_date.cmp(&a_date);
         }
         std::cmp::Ordering::Equal
     });
@@ -248,6 +399,11 @@ pub fn export() -> Templates {
         vec![TemplateDataType::DirectoryData],
         BlogListPreset,
     );
+    templates.template(
+        "tag",
+        vec![TemplateDataType::DirectoryData],
+        BlogTagPreset,
+    );
     templates.template(
         "content",
         vec![TemplateDataType::Markdown],
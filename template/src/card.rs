@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashMap};
+
+use dioxus::prelude::*;
+use dioxus_retrouter::Link;
+use karaty_blueprint::{
+    config::ExternalLinkConfig, TemplateDataType, TemplateProps, Templates, Value,
+};
+use serde::Deserialize;
+
+use crate::utils::json::error_snippet;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CardInfo {
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub footnote: String,
+    /// cover image displayed at the top of the card.
+    #[serde(default)]
+    pub image: Option<String>,
+    /// link target, `_self` with an internal path (start with `/`) will navigate in app.
+    #[serde(default = "default_target")]
+    pub target: String,
+}
+
+fn default_target() -> String {
+    String::from("_blank")
+}
+
+/// card json support a plain card list or grouped card lists.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum CardList {
+    List(Vec<CardInfo>),
+    Group(BTreeMap<String, Vec<CardInfo>>),
+}
+
+const DEFAULT_COLUMNS: i64 = 3;
+
+/// responsive grid class from `columns` config (1 - 4), the count is used on large screens.
+fn grid_class(config: &HashMap<String, Value>) -> &'static str {
+    let columns = match config.get("columns") {
+        Some(Value::Integer(columns)) if (1..=4).contains(columns) => *columns,
+        Some(value) => {
+            log::warn!("`columns` config must be an integer in 1 - 4, got `{}`", value);
+            DEFAULT_COLUMNS
+        }
+        None => DEFAULT_COLUMNS,
+    };
+    match columns {
+        1 => "grid-cols-1",
+        2 => "grid-cols-1 md:grid-cols-2",
+        4 => "grid-cols-1 sm:grid-cols-2 lg:grid-cols-4",
+        _ => "grid-cols-1 sm:grid-cols-2 lg:grid-cols-3",
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn ProjectsPreset(cx: Scope<TemplateProps>) -> Element {
+    let Navbar = cx.props.utility.navbar;
+    let Footer = cx.props.utility.footer;
+    let Error = cx.props.utility.error;
+
+    let content = cx.props.data.text();
+    let groups = match serde_json::from_str::<CardList>(&content) {
+        Ok(CardList::List(list)) => vec![(None, list)],
+        Ok(CardList::Group(group)) => group.into_iter().map(|(k, v)| (Some(k), v)).collect(),
+        Err(e) => {
+            return cx.render(rsx! {
+                Error {
+                    title: "Card Parse Failed".to_string(),
+                    content: e.to_string(),
+                    detail: Some(error_snippet(&content, &e)),
+                }
+            });
+        }
+    };
+
+    let title = match cx.props.config.get("title") {
+        Some(Value::String(title)) => Some(title.clone()),
+        _ => None,
+    };
+    let title = title.map(|title| {
+        rsx! {
+            h1 { class: "text-3xl font-bold text-center dark:text-white", "{title}" }
+        }
+    });
+
+    let external_link = &cx.props.utility.app_config.site.external_link;
+    let grid = grid_class(&cx.props.config);
+    let groups = groups.into_iter().map(|(name, list)| {
+        let name = name.map(|name| {
+            rsx! {
+                h2 { class: "mt-8 mb-4 text-2xl font-semibold dark:text-white", "{name}" }
+            }
+        });
+        let cards = list.into_iter().map(|card| {
+            let external_link = external_link.clone();
+            rsx! { Card { info: card, external_link: external_link } }
+        });
+        rsx! {
+            name
+            div { class: "grid {grid} gap-4", cards }
+        }
+    });
+
+    let page_class = crate::page_class(&cx.props.config);
+    let max_width = crate::max_width_class(&cx.props.config, "max-w-7xl");
+
+    cx.render(rsx! {
+        section { class: "bg-cover bg-white dark:bg-gray-900 {page_class}",
+            Navbar {}
+            div { class: "container mx-auto px-8 {max_width}",
+                title
+                groups
+            }
+            div { class: "flex justify-center container mx-auto my-14", Footer {} }
+        }
+    })
+}
+
+#[derive(Props, PartialEq)]
+pub struct CardProps {
+    info: CardInfo,
+    external_link: ExternalLinkConfig,
+}
+
+#[allow(non_snake_case)]
+pub fn Card(cx: Scope<CardProps>) -> Element {
+    let info = &cx.props.info;
+    let external = &cx.props.external_link;
+    let class = "block h-full p-5 rounded-lg bg-gray-50 hover:bg-gray-100 \
+    dark:bg-gray-800 dark:hover:bg-gray-700";
+    let icon = external.show_icon(&info.url).then(|| {
+        rsx! {
+            span { class: "ml-1 text-xs text-gray-400", "aria-hidden": "true", "↗" }
+        }
+    });
+    let image = info.image.as_ref().map(|image| {
+        rsx! {
+            img {
+                class: "w-full h-40 mb-4 object-cover rounded-md",
+                src: "{image}",
+                alt: "{info.title}",
+                "loading": "lazy",
+            }
+        }
+    });
+    let body = rsx! {
+        image
+        h3 { class: "text-lg font-semibold text-gray-800 dark:text-white", "{info.title}", icon }
+        p { class: "mt-2 text-sm text-gray-600 dark:text-gray-300", "{info.content}" }
+        p { class: "mt-4 text-xs text-gray-400", "{info.footnote}" }
+    };
+    if info.target == "_self" && info.url.starts_with('/') {
+        let url = crate::site_link(cx, &info.url);
+        cx.render(rsx! {
+            Link { class: "{class}", to: "{url}", body }
+        })
+    } else {
+        let rel = external.rel(&info.url, &info.target);
+        cx.render(rsx! {
+            a { class: "{class}", href: "{info.url}", target: "{info.target}", rel: "{rel}", body }
+        })
+    }
+}
+
+pub fn export() -> Templates {
+    let mut templates = Templates::new();
+
+    templates.template("projects", vec![TemplateDataType::Json], ProjectsPreset);
+
+    templates
+}
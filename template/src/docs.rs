@@ -2,10 +2,13 @@ use std::collections::HashMap;
 
 use dioxus::prelude::*;
 use dioxus_retrouter::Link;
-use karaty_blueprint::TemplateProps;
+use karaty_blueprint::{
+    front_matter::{meta_string, parse_front_matter},
+    TemplateProps, Value,
+};
 use markdown::mdast;
 
-use crate::blog::PostInfo;
+use crate::{blog::PostInfo, scroll, utils::date::format_date};
 
 #[allow(non_snake_case)]
 pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
@@ -17,6 +20,7 @@ pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
 
     let data = &cx.props.data;
     let config = cx.props.config.clone();
+    let scroll_to_top = scroll::enabled(&config);
 
     let segment_name = if let Some(karaty_blueprint::Value::String(v)) = config.get("file-segment")
     {
@@ -53,6 +57,15 @@ pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
             vec![]
         }
     };
+    // file path is passed to renderer, relative links will be resolved by it
+    let mut renderer_config = HashMap::new();
+    renderer_config.insert(
+        "path".to_string(),
+        karaty_blueprint::Value::String(file_path.join("/")),
+    );
+    if let Some(value) = config.get("line-numbers") {
+        renderer_config.insert("line-numbers".to_string(), value.clone());
+    }
     let data = data.get(file_path);
 
     match data {
@@ -68,7 +81,10 @@ pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
             let date = if data.date.is_empty() {
                 "Unknown".to_string()
             } else {
-                data.date
+                format_date(
+                    &data.date,
+                    cx.props.utility.app_config.site.date_format.as_deref(),
+                )
             };
 
             cx.render(rsx! {
@@ -108,7 +124,7 @@ pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
                                     class: "prose prose-sm sm:prose-base mt-4 dark:text-white dark:prose-invert",
                                     Markdown {
                                         content: data.content.clone(),
-                                        config: Default::default(),
+                                        config: renderer_config,
                                     }
                                 }
                             }
@@ -120,6 +136,9 @@ pub fn DocsPreset(cx: Scope<TemplateProps>) -> Element {
                         class: "flex justify-center container mx-auto my-14",
                         Footer {}
                     }
+                    if scroll_to_top {
+                        rsx! { scroll::ScrollToTop {} }
+                    }
                 }
             })
         }
@@ -175,6 +194,7 @@ pub fn DocsSideBar(cx: Scope<SideBarProps>) -> Element {
             let class = "text-gray-600 dark:text-gray-200 hover:text-blue-700 dark:hover:text-blue-300";
             if &link.url[0..1] == "@" {
                 let url = cx.props.path.replace(&format!(":{}", cx.props.file_sign), &link.url[1..]);
+                let url = crate::site_link(cx, &url);
                 return rsx! {
                     Link {
                         class: "{class}",
@@ -205,72 +225,34 @@ pub fn DocsSideBar(cx: Scope<SideBarProps>) -> Element {
 }
 
 fn to_info(meta_info: String) -> Option<PostInfo> {
-    let mut type_mark = HashMap::new();
-
-    type_mark.insert("title".into(), "string");
-    type_mark.insert("tags".into(), "array");
-    type_mark.insert("category".into(), "string");
-    type_mark.insert("date".into(), "string");
-    type_mark.insert("released".into(), "bool");
-
-    let temp = markdown_meta_parser::MetaData {
-        content: meta_info,
-        required: vec!["title".to_string()],
-        type_mark,
-    }
-    .parse()
-    .ok();
+    let (meta_info, content) = parse_front_matter(&meta_info)?;
 
-    if temp.is_none() {
-        return None;
-    }
-    let (meta_info, content) = temp.unwrap();
-
-    if meta_info.get("released").is_some()
-        && meta_info
-            .get("released")
-            .unwrap()
-            .clone()
-            .as_bool()
-            .unwrap()
-            == false
-    {
+    if let Some(Value::Boolean(false)) = meta_info.get("released") {
         return None;
     }
 
-    let title = meta_info.get("title").unwrap().clone();
+    let title = meta_string(&meta_info, "title")?;
+    let date = meta_string(&meta_info, "date").unwrap_or_default();
 
-    let date = meta_info.get("date");
-    let date = if let Some(d) = date {
-        d.clone().as_string().unwrap()
-    } else {
-        "".to_string()
+    let tags = match meta_info.get("tags") {
+        Some(Value::Array(list)) => list
+            .iter()
+            .filter_map(|v| v.as_str().map(|v| v.to_string()))
+            .collect(),
+        _ => vec![],
     };
 
-    let tags = meta_info.get("tags");
-    let tags = if let Some(v) = tags {
-        v.clone().as_array().unwrap()
-    } else {
-        vec![]
-    };
+    let category = meta_string(&meta_info, "category");
 
-    let category = meta_info.get("category");
-    let category = if let Some(v) = category {
-        v.clone().as_string()
-    } else {
-        None
-    };
-
-    let title = title.as_string().unwrap();
-
-    let blog_info = PostInfo {
+    Some(PostInfo {
         title,
         tags,
         category,
         date,
         path: String::new(),
         content,
+        excerpt: String::new(),
+        author: None,
         sub_group: Default::default(),
-    };
-    return Some(blog_info);
+    })
 }
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use dioxus::prelude::*;
+use karaty_blueprint::{TemplateProps, Value};
+use serde::Deserialize;
+
+use crate::utils::json::error_snippet;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GalleryData {
+    pub src: String,
+    #[serde(default)]
+    pub alt: String,
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+/// gallery json support a plain image list or grouped image lists.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum GalleryList {
+    List(Vec<GalleryData>),
+    Group(BTreeMap<String, Vec<GalleryData>>),
+}
+
+#[allow(non_snake_case)]
+pub fn GalleryPreset(cx: Scope<TemplateProps>) -> Element {
+    let Navbar = cx.props.utility.navbar;
+    let Footer = cx.props.utility.footer;
+    let Error = cx.props.utility.error;
+
+    let content = cx.props.data.text();
+    let groups = match serde_json::from_str::<GalleryList>(&content) {
+        Ok(GalleryList::List(list)) => vec![(None, list)],
+        Ok(GalleryList::Group(group)) => group.into_iter().map(|(k, v)| (Some(k), v)).collect(),
+        Err(e) => {
+            return cx.render(rsx! {
+                Error {
+                    title: "Gallery Parse Failed".to_string(),
+                    content: e.to_string(),
+                    detail: Some(error_snippet(&content, &e)),
+                }
+            });
+        }
+    };
+
+    let title = match cx.props.config.get("title") {
+        Some(Value::String(title)) => Some(title.clone()),
+        _ => None,
+    };
+    let title = title.map(|title| {
+        rsx! {
+            h1 { class: "text-3xl font-bold text-center dark:text-white", "{title}" }
+        }
+    });
+
+    let groups = groups.into_iter().map(|(name, list)| {
+        let name = name.map(|name| {
+            rsx! {
+                h2 { class: "mt-8 mb-4 text-2xl font-semibold dark:text-white", "{name}" }
+            }
+        });
+        let images = list.into_iter().map(|image| {
+            let caption = image.caption.map(|caption| {
+                rsx! {
+                    figcaption {
+                        class: "mt-2 text-sm text-center text-gray-500 dark:text-gray-300",
+                        "{caption}"
+                    }
+                }
+            });
+            rsx! {
+                figure { class: "mb-4 break-inside-avoid",
+                    img {
+                        class: "w-full rounded-lg",
+                        src: "{image.src}",
+                        alt: "{image.alt}",
+                        "loading": "lazy",
+                    }
+                    caption
+                }
+            }
+        });
+        rsx! {
+            name
+            div { class: "columns-1 sm:columns-2 lg:columns-3 gap-4", images }
+        }
+    });
+
+    cx.render(rsx! {
+        section { class: "bg-cover bg-white dark:bg-gray-900",
+            Navbar {}
+            div { class: "container mx-auto px-8 max-w-7xl",
+                title
+                groups
+            }
+            div { class: "flex justify-center container mx-auto my-14", Footer {} }
+        }
+    })
+}
@@ -1,8 +1,19 @@
+use std::collections::HashMap;
+
 use dioxus::prelude::*;
-use karaty_blueprint::{TemplateDataType, TemplateProps, Templates};
+use karaty_blueprint::{config::SiteConfig, TemplateDataType, TemplateProps, Templates, Value};
 
 mod blog;
+mod card;
 mod docs;
+mod gallery;
+mod navigation;
+mod raw;
+mod scroll;
+mod sidebar;
+mod utils;
+
+pub use utils::date::format_date;
 
 const AVAILABLE_STYLE_SETTINGS: [&'static str; 26] = [
     "headings",
@@ -33,35 +44,122 @@ const AVAILABLE_STYLE_SETTINGS: [&'static str; 26] = [
     "hr",
 ];
 
+pub const DEFAULT_PROSE_CLASS: &str = "prose prose-sm sm:prose-base dark:prose-invert";
+
+/// generate prose class from style table, `base` key can override the default base classes.
 pub fn generate_prose_class(config: toml::map::Map<String, toml::Value>) -> String {
-    let mut res = String::from("prose prose-sm sm:prose-base dark:prose-invert");
+    let mut res = match config.get("base") {
+        Some(toml::Value::String(base)) => base.trim().to_string(),
+        _ => String::from(DEFAULT_PROSE_CLASS),
+    };
+    for key in unknown_style_keys(&config) {
+        log::warn!(
+            "unknown prose style key `{}`, available keys: {}",
+            key,
+            AVAILABLE_STYLE_SETTINGS.join(", ")
+        );
+    }
     for i in AVAILABLE_STYLE_SETTINGS {
         if let Some(toml::Value::String(v)) = config.get(i) {
-            let list = v.split(" ").collect::<Vec<&str>>();
-            if list.len() >= 1 {
-                res.push_str(&format!(" prose-{i}:{}", list.get(0).unwrap()))
-            } else {
-                res.push_str(&format!("{} ", list.join(&format!(" prose-{i}:"))));
+            for class in v.split_whitespace() {
+                res.push_str(&format!(" prose-{i}:{class}"));
             }
         }
     }
     res
 }
 
-#[allow(non_snake_case)]
+/// style keys which are not `base` or one of `AVAILABLE_STYLE_SETTINGS`.
+fn unknown_style_keys(config: &toml::map::Map<String, toml::Value>) -> Vec<&str> {
+    config
+        .keys()
+        .map(|key| key.as_str())
+        .filter(|key| *key != "base" && !AVAILABLE_STYLE_SETTINGS.contains(key))
+        .collect()
+}
+
+/// internal link target with `site.base-path` prepended, path is kept when site config isn't provided.
+pub(crate) fn site_link(cx: &ScopeState, path: &str) -> String {
+    match cx.consume_context::<SiteConfig>() {
+        Some(site) => site.with_base(path),
+        None => path.to_string(),
+    }
+}
+
+/// extra class of the page wrapper from `class` config, non-string value is ignored.
+pub(crate) fn page_class(config: &HashMap<String, Value>) -> String {
+    match config.get("class") {
+        Some(Value::String(class)) => class.trim().to_string(),
+        Some(value) => {
+            log::warn!("`class` config must be a string, got `{}`", value);
+            String::new()
+        }
+        None => String::new(),
+    }
+}
+
+const AVAILABLE_MAX_WIDTHS: [&str; 15] = [
+    "xs",
+    "sm",
+    "md",
+    "lg",
+    "xl",
+    "2xl",
+    "3xl",
+    "4xl",
+    "5xl",
+    "6xl",
+    "7xl",
+    "full",
+    "prose",
+    "screen-lg",
+    "none",
+];
+
+/// `max-w-*` class of the content wrapper from `max-width` config (e.g. `"3xl"`),
+/// `default` is used when it's not set or invalid.
+pub(crate) fn max_width_class(config: &HashMap<String, Value>, default: &str) -> String {
+    match config.get("max-width") {
+        Some(Value::String(width)) if AVAILABLE_MAX_WIDTHS.contains(&width.as_str()) => {
+            format!("max-w-{width}")
+        }
+        Some(value) => {
+            log::warn!(
+                "unknown `max-width` config `{}`, available values: {}",
+                value,
+                AVAILABLE_MAX_WIDTHS.join(", ")
+            );
+            default.to_string()
+        }
+        None => default.to_string(),
+    }
+}
+
 pub fn centered_display(cx: Scope<TemplateProps>) -> Element {
+    centered_page(cx, "markdown")
+}
+
+/// html content in the same page chrome as `centered_display`.
+pub fn centered_html(cx: Scope<TemplateProps>) -> Element {
+    centered_page(cx, "html")
+}
+
+#[allow(non_snake_case)]
+fn centered_page<'a>(cx: Scope<'a, TemplateProps>, renderer: &str) -> Element<'a> {
     let config = &cx.props.config;
 
     let Navbar = cx.props.utility.navbar;
     let Footer = cx.props.utility.footer;
-    let Markdown = cx.props.utility.renderers.get("markdown").unwrap().clone();
+    let Giscus = cx.props.utility.giscus;
+    let LastUpdated = cx.props.utility.last_updated;
+    let Renderer = *cx.props.utility.renderers.get(renderer).unwrap();
 
     let content = cx.props.data.text();
 
     let class = if let Some(toml::Value::Table(t)) = config.get("style") {
         generate_prose_class(t.clone())
     } else {
-        "prose prose-sm sm:prose-base dark:prose-invert".to_string()
+        DEFAULT_PROSE_CLASS.to_string()
     };
 
     let hide_navbar = if let Some(toml::Value::Boolean(b)) = config.get("hide-navbar") {
@@ -76,19 +174,103 @@ pub fn centered_display(cx: Scope<TemplateProps>) -> Element {
         false
     };
 
+    let breadcrumbs = if let Some(toml::Value::Boolean(true)) = config.get("breadcrumbs") {
+        Some(cx.props.route.access_path.clone())
+    } else {
+        None
+    };
+    let breadcrumbs = breadcrumbs.map(|path| {
+        rsx! {
+            navigation::Breadcrumbs { path: path }
+        }
+    });
+
+    // previous & next page come from `pages` config
+    let page_navigation = if let Some(toml::Value::Array(pages)) = config.get("pages") {
+        let pages = pages
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.trim_end_matches('/').to_string())
+            .collect::<Vec<String>>();
+        let pages = match config.get("order") {
+            Some(toml::Value::String(order)) if order == "alphabetical" => {
+                navigation::ordered_pages(pages, None)
+            }
+            _ => pages,
+        };
+        let current = cx.props.route.access_path.trim_end_matches('/').to_string();
+        let (prev, next) = navigation::neighbors(&pages, &current);
+        let prev = prev.map(|v| (navigation::page_title(v), v.clone()));
+        let next = next.map(|v| (navigation::page_title(v), v.clone()));
+        if prev.is_some() || next.is_some() {
+            Some(rsx! {
+                navigation::PageNavigation { prev: prev, next: next }
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    // `tags` in front matter are displayed after content
+    let tags = match karaty_blueprint::front_matter::parse_front_matter(&content) {
+        Some((meta, _)) => match meta.get("tags") {
+            Some(toml::Value::Array(list)) => list
+                .iter()
+                .filter_map(|v| v.as_str().map(|v| v.to_string()))
+                .collect::<Vec<String>>(),
+            _ => vec![],
+        },
+        None => vec![],
+    };
+    let tags = if tags.is_empty() {
+        None
+    } else {
+        let link = blog::tag_link(config);
+        Some(rsx! {
+            p { class: "mt-4", blog::TagList { tags: tags, link: link } }
+        })
+    };
+
+    // date of the latest commit which changed the content (GitHub data source only)
+    let last_updated = matches!(config.get("last-updated"), Some(toml::Value::Boolean(true)));
+
+    let scroll_to_top = scroll::enabled(config);
+    // comment thread of current page, nothing is displayed when `[giscus]` is not configured
+    let comments = matches!(config.get("comments"), Some(toml::Value::Boolean(true)));
+
+    let page_class = page_class(config);
+    let max_width = max_width_class(config, "");
+
     cx.render(rsx! {
-        section { class: "bg-cover bg-white dark:bg-gray-900",
+        section { class: "bg-cover bg-white dark:bg-gray-900 {page_class}",
             if !hide_navbar {
                 rsx! { Navbar {} }
             }
-            div { class: "flex w-full items-center justify-center container mx-auto px-8",
+            div { class: "flex w-full items-center justify-center container mx-auto px-8 {max_width}",
                 div { class: "text-center",
-                    div { class: "{class}", Markdown { content: content, config: Default::default() } }
+                    breadcrumbs,
+                    div { class: "{class}", Renderer { content: content, config: config.clone() } }
+                    tags,
+                    if last_updated {
+                        rsx! { LastUpdated {} }
+                    }
+                    page_navigation,
+                    if comments {
+                        rsx! {
+                            Giscus {}
+                            div { class: "giscus flex justify-center container mx-auto my-12" }
+                        }
+                    }
                     if !hide_footer {
                         rsx! { Footer {} }
                     }
                 }
             }
+            if scroll_to_top {
+                rsx! { scroll::ScrollToTop {} }
+            }
         }
     })
 }
@@ -97,13 +279,81 @@ pub fn export() -> Templates {
     let mut list = Templates::new();
 
     list.template("center", vec![TemplateDataType::Markdown], centered_display);
+    list.template("center", vec![TemplateDataType::HTML], centered_html);
 
     list.template(
         "docs",
         vec![TemplateDataType::DirectoryData],
         docs::DocsPreset,
     );
+    list.template(
+        "gallery",
+        vec![TemplateDataType::Json],
+        gallery::GalleryPreset,
+    );
+    list.template(
+        "sidebar",
+        vec![TemplateDataType::DirectoryData],
+        sidebar::SidebarPreset,
+    );
+    list.template(
+        "blog",
+        vec![TemplateDataType::DirectoryData],
+        blog::BlogListPreset,
+    );
+    list.template(
+        "raw",
+        vec![
+            TemplateDataType::Json,
+            TemplateDataType::HTML,
+            TemplateDataType::Markdown,
+        ],
+        raw::RawPreset,
+    );
     list.sub_module("blog", blog::export());
+    list.sub_module("card", card::export());
 
     list
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(source: &str) -> toml::map::Map<String, toml::Value> {
+        toml::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn unknown_style_keys_are_reported() {
+        let config = style("headding = \"text-lg\"\np = \"text-lg\"\nbase = \"prose\"");
+        assert_eq!(unknown_style_keys(&config), ["headding"]);
+        assert_eq!(generate_prose_class(config), "prose prose-p:text-lg");
+    }
+
+    #[test]
+    fn prose_class_single_token() {
+        let config = style("p = \"text-lg\"");
+        assert_eq!(
+            generate_prose_class(config),
+            format!("{DEFAULT_PROSE_CLASS} prose-p:text-lg")
+        );
+    }
+
+    #[test]
+    fn prose_class_multiple_tokens() {
+        let config = style("p = \"text-lg  font-bold\"\nh1 = \"text-red-500\"");
+        assert_eq!(
+            generate_prose_class(config),
+            format!(
+                "{DEFAULT_PROSE_CLASS} prose-h1:text-red-500 prose-p:text-lg prose-p:font-bold"
+            )
+        );
+    }
+
+    #[test]
+    fn known_style_keys_only() {
+        let config = style("h1 = \"text-lg\"\na = \"underline\"");
+        assert!(unknown_style_keys(&config).is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use dioxus::prelude::*;
+use dioxus_retrouter::Link;
+use karaty_blueprint::Value;
+
+/// display name for a path segment: `getting-started` -> `Getting Started`
+fn segment_title(segment: &str) -> String {
+    segment
+        .split(['-', '_'])
+        .filter(|v| !v.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// breadcrumb list from access path: (title, link)
+fn breadcrumb_list(path: &str) -> Vec<(String, String)> {
+    let mut result = vec![("Home".to_string(), "/".to_string())];
+    let mut link = String::new();
+    for segment in path.split('/').filter(|v| !v.is_empty()) {
+        link.push_str(&format!("/{segment}"));
+        result.push((segment_title(segment), link.clone()));
+    }
+    result
+}
+
+#[derive(Props, PartialEq)]
+pub struct BreadcrumbsProps {
+    path: String,
+}
+
+#[allow(non_snake_case)]
+pub fn Breadcrumbs(cx: Scope<BreadcrumbsProps>) -> Element {
+    let list = breadcrumb_list(&cx.props.path);
+    let last = list.len() - 1;
+    let items = list.into_iter().enumerate().map(move |(index, (title, link))| {
+        if index == last {
+            rsx! {
+                li { class: "text-gray-700 dark:text-gray-200", "{title}" }
+            }
+        } else {
+            let link = crate::site_link(cx, &link);
+            rsx! {
+                li {
+                    Link { class: "hover:text-blue-700 dark:hover:text-blue-300", to: "{link}", "{title}" }
+                    span { class: "mx-2", "/" }
+                }
+            }
+        }
+    });
+    cx.render(rsx! {
+        nav { class: "text-sm text-left text-gray-500 dark:text-gray-400 mt-4",
+            ol { class: "flex flex-wrap", items }
+        }
+    })
+}
+
+/// sort page list by `order` config: an explicit list (unlisted pages follow alphabetically)
+/// or `"alphabetical"` (default).
+pub fn ordered_pages(mut pages: Vec<String>, order: Option<&Value>) -> Vec<String> {
+    pages.sort();
+    if let Some(Value::Array(order)) = order {
+        let order = order
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect::<Vec<&str>>();
+        pages.sort_by_key(|page| order.iter().position(|v| v == page).unwrap_or(order.len()));
+    }
+    pages
+}
+
+/// previous & next page of current page in the ordered list.
+pub fn neighbors<'a>(
+    list: &'a [String],
+    current: &str,
+) -> (Option<&'a String>, Option<&'a String>) {
+    match list.iter().position(|v| v == current) {
+        Some(index) => (
+            index.checked_sub(1).and_then(|i| list.get(i)),
+            list.get(index + 1),
+        ),
+        None => (None, None),
+    }
+}
+
+/// page title & link
+pub type PageLink = (String, String);
+
+/// title for a page path, use the last segment of path.
+pub fn page_title(path: &str) -> String {
+    segment_title(
+        path.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default(),
+    )
+}
+
+#[derive(Props, PartialEq)]
+pub struct PageNavigationProps {
+    #[props(!optional)]
+    prev: Option<PageLink>,
+    #[props(!optional)]
+    next: Option<PageLink>,
+}
+
+#[allow(non_snake_case)]
+pub fn PageNavigation(cx: Scope<PageNavigationProps>) -> Element {
+    let prev = cx.props.prev.clone().map(|(title, link)| {
+        let link = crate::site_link(cx, &link);
+        rsx! {
+            Link {
+                class: "text-left hover:text-blue-700 dark:hover:text-blue-300",
+                to: "{link}",
+                "← {title}"
+            }
+        }
+    });
+    let next = cx.props.next.clone().map(|(title, link)| {
+        let link = crate::site_link(cx, &link);
+        rsx! {
+            Link {
+                class: "ml-auto text-right hover:text-blue-700 dark:hover:text-blue-300",
+                to: "{link}",
+                "{title} →"
+            }
+        }
+    });
+    cx.render(rsx! {
+        nav { class: "flex text-sm text-gray-600 dark:text-gray-300 mt-8 pt-4 border-t dark:border-gray-700",
+            prev,
+            next
+        }
+    })
+}
@@ -0,0 +1,48 @@
+use dioxus::prelude::*;
+use karaty_blueprint::{TemplateProps, Value};
+
+use crate::{max_width_class, page_class, DEFAULT_PROSE_CLASS};
+
+/// display file content verbatim, json content will be pretty-printed.
+#[allow(non_snake_case)]
+pub fn RawPreset(cx: Scope<TemplateProps>) -> Element {
+    let config = &cx.props.config;
+
+    let Navbar = cx.props.utility.navbar;
+    let Footer = cx.props.utility.footer;
+
+    let content = cx.props.data.text();
+    let content = match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(value) if value.is_object() || value.is_array() => {
+            serde_json::to_string_pretty(&value).unwrap_or(content)
+        }
+        _ => content,
+    };
+
+    let title = match config.get("title") {
+        Some(Value::String(title)) => Some(title.clone()),
+        _ => None,
+    };
+    let title = title.map(|title| {
+        rsx! {
+            h1 { class: "text-3xl font-bold text-center dark:text-white", "{title}" }
+        }
+    });
+
+    let page_class = page_class(config);
+    let max_width = max_width_class(config, "max-w-4xl");
+
+    // text node is escaped, html content is displayed as source code
+    cx.render(rsx! {
+        section { class: "bg-cover bg-white dark:bg-gray-900 {page_class}",
+            Navbar {}
+            div { class: "container mx-auto px-8 py-6 {max_width}",
+                title,
+                div { class: "{DEFAULT_PROSE_CLASS} max-w-none mt-6",
+                    pre { class: "overflow-x-auto text-left", code { "{content}" } }
+                }
+                Footer {}
+            }
+        }
+    })
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use gloo_events::EventListener;
+use karaty_blueprint::Value;
+
+/// default scroll distance (px) before the button appears.
+pub const DEFAULT_THRESHOLD: f64 = 400.0;
+
+/// `scroll-to-top = true` config enables the button.
+pub(crate) fn enabled(config: &HashMap<String, Value>) -> bool {
+    matches!(config.get("scroll-to-top"), Some(Value::Boolean(true)))
+}
+
+#[derive(Props, PartialEq)]
+pub struct ScrollToTopProps {
+    #[props(default = DEFAULT_THRESHOLD)]
+    threshold: f64,
+}
+
+/// "back to top" button fixed in the bottom-right corner, displayed after scrolling past `threshold`.
+#[allow(non_snake_case)]
+pub fn ScrollToTop(cx: Scope<ScrollToTopProps>) -> Element {
+    let visible = use_state(cx, || false);
+
+    let threshold = cx.props.threshold;
+    // listener is removed when the hook is dropped (component unmounted)
+    cx.use_hook(|| {
+        let window = web_sys::window()?;
+        let visible = visible.clone();
+        let target = window.clone();
+        Some(EventListener::new(&target, "scroll", move |_| {
+            let offset = window.scroll_y().unwrap_or_default();
+            if (offset > threshold) != *visible.current() {
+                visible.set(offset > threshold);
+            }
+        }))
+    });
+
+    let state = if *visible.get() {
+        "opacity-100 translate-y-0"
+    } else {
+        "opacity-0 translate-y-4 pointer-events-none"
+    };
+
+    cx.render(rsx! {
+        button {
+            class: "karaty-no-print fixed bottom-6 right-6 z-40 w-10 h-10 rounded-full shadow-md bg-white text-gray-600 hover:text-blue-700 dark:bg-gray-800 dark:text-gray-300 dark:hover:text-blue-300 transition duration-300 motion-reduce:transition-none {state}",
+            title: "Back to top",
+            "aria-label": "Back to top",
+            onclick: move |_| {
+                if let Some(window) = web_sys::window() {
+                    let options = web_sys::ScrollToOptions::new();
+                    options.set_top(0.0);
+                    options.set_behavior(web_sys::ScrollBehavior::Smooth);
+                    window.scroll_to_with_scroll_to_options(&options);
+                }
+            },
+            "↑"
+        }
+    })
+}
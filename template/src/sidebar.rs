@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use dioxus_retrouter::Link;
+use karaty_blueprint::{TemplateData, TemplateProps};
+
+use crate::{generate_prose_class, navigation, scroll, DEFAULT_PROSE_CLASS};
+
+#[allow(non_snake_case)]
+pub fn SidebarPreset(cx: Scope<TemplateProps>) -> Element {
+    let _404 = cx.props.utility._404;
+    let Navbar = cx.props.utility.navbar;
+    let Footer = cx.props.utility.footer;
+    let Markdown = cx.props.utility.renderers.get("markdown").unwrap().clone();
+
+    let config = &cx.props.config;
+
+    let segment_name = if let Some(toml::Value::String(v)) = config.get("file-segment") {
+        v.to_string()
+    } else {
+        "path".to_string()
+    };
+
+    let class = if let Some(toml::Value::Table(t)) = config.get("style") {
+        generate_prose_class(t.clone())
+    } else {
+        DEFAULT_PROSE_CLASS.to_string()
+    };
+
+    let dir = if let TemplateData::Directory(dir) = &cx.props.data {
+        dir
+    } else {
+        let display_error = cx.props.utility.error;
+        return cx.render(rsx! {
+            display_error {
+                title: format!("Unrecognized data type"),
+                content: format!("sidebar template must load by Directory data-type")
+            }
+        });
+    };
+
+    // sidebar links come from the markdown files in bound directory
+    let mut pages = dir
+        .iter()
+        .filter(|(name, data)| {
+            matches!(data, TemplateData::File(_)) && name.ends_with(".md") && !name.starts_with('_')
+        })
+        .map(|(name, _)| name.trim_end_matches(".md").to_string())
+        .collect::<Vec<String>>();
+    pages = navigation::ordered_pages(pages, config.get("order"));
+
+    let current = match cx.props.route.segments.get(&segment_name) {
+        Some(v) => v.clone(),
+        None => match pages.first() {
+            Some(v) => v.clone(),
+            None => return cx.render(rsx! { _404 {} }),
+        },
+    };
+
+    let mut file_path = current.split('/').map(String::from).collect::<Vec<String>>();
+    if let Some(last) = file_path.last_mut() {
+        *last = format!("{last}.md");
+    }
+    // file path is passed to renderer, relative links will be resolved by it
+    let mut renderer_config = HashMap::new();
+    renderer_config.insert("path".to_string(), toml::Value::String(file_path.join("/")));
+    if let Some(value) = config.get("line-numbers") {
+        renderer_config.insert("line-numbers".to_string(), value.clone());
+    }
+    let content = match cx.props.data.get(file_path) {
+        Some(TemplateData::File(content)) => content,
+        _ => return cx.render(rsx! { _404 {} }),
+    };
+
+    let page_url = |name: &str| {
+        cx.props
+            .route
+            .bound_path
+            .replace(&format!(":{}", segment_name), name)
+    };
+    let (prev, next) = navigation::neighbors(&pages, &current);
+    let prev = prev.map(|name| (name.clone(), page_url(name)));
+    let next = next.map(|name| (name.clone(), page_url(name)));
+
+    let links = pages.iter().map(|name| {
+        let url = crate::site_link(cx, &page_url(name));
+        let class = if name == &current {
+            "font-semibold text-blue-700 dark:text-blue-300"
+        } else {
+            "text-gray-600 dark:text-gray-200 hover:text-blue-700 dark:hover:text-blue-300"
+        };
+        rsx! {
+            li {
+                class: "text-sm font-mono py-1",
+                Link {
+                    class: "{class}",
+                    to: "{url}",
+                    "{name}"
+                }
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div { class: "bg-cover bg-white dark:bg-gray-900 dark:text-white",
+            Navbar {}
+            div { class: "container mx-auto px-8 max-w-7xl",
+                div { class: "grid grid-cols-12 gap-6",
+                    div {
+                        class: "col-span-12 sm:col-span-3 bg-gray-50 dark:bg-gray-800 rounded-md",
+                        ul {
+                            class: "px-5 py-2",
+                            links
+                        }
+                    }
+                    div {
+                        class: "col-span-12 sm:col-span-9",
+                        div {
+                            class: "{class}",
+                            Markdown {
+                                content: content,
+                                config: renderer_config,
+                            }
+                        }
+                        navigation::PageNavigation { prev: prev, next: next }
+                    }
+                }
+            }
+            div {
+                class: "flex justify-center container mx-auto my-14",
+                Footer {}
+            }
+            if scroll::enabled(config) {
+                rsx! { scroll::ScrollToTop {} }
+            }
+        }
+    })
+}
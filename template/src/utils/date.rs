@@ -0,0 +1,48 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+/// datetime formats accepted besides RFC 3339.
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+/// date formats accepted, time is set to midnight.
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%Y/%m/%d"];
+
+/// parse a front matter date: ISO 8601 (with or without time & offset) or `YYYY-MM-DD`.
+pub fn parse_date(date: &str) -> Option<NaiveDateTime> {
+    let date = date.trim();
+    if let Ok(value) = DateTime::parse_from_rfc3339(date) {
+        return Some(value.naive_local());
+    }
+    for format in DATETIME_FORMATS {
+        if let Ok(value) = NaiveDateTime::parse_from_str(date, format) {
+            return Some(value);
+        }
+    }
+    for format in DATE_FORMATS {
+        if let Ok(value) = NaiveDate::parse_from_str(date, format) {
+            return value.and_hms_opt(0, 0, 0);
+        }
+    }
+    None
+}
+
+/// format date with `strftime` pattern (e.g. `%B %d, %Y`),
+/// the raw string is returned when no pattern, invalid date or invalid pattern.
+pub fn format_date(date: &str, format: Option<&str>) -> String {
+    let (Some(format), Some(value)) = (format, parse_date(date)) else {
+        return date.to_string();
+    };
+    // unsupported pattern (e.g. timezone of naive date) is a format error, not a panic
+    let mut result = String::new();
+    if write!(result, "{}", value.format(format)).is_err() {
+        log::warn!("invalid `date-format` pattern `{}`", format);
+        return date.to_string();
+    }
+    result
+}
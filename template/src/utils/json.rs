@@ -0,0 +1,51 @@
+/// max characters of every line in error snippet.
+const SNIPPET_WIDTH: usize = 80;
+/// lines displayed before the error line.
+const SNIPPET_CONTEXT: usize = 2;
+
+/// byte offset of the error location in content.
+fn error_offset(content: &str, line: usize, column: usize) -> usize {
+    let line_start = content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(|v| v.len())
+        .sum::<usize>();
+    let column_bytes = content[line_start..]
+        .chars()
+        .take(column.saturating_sub(1))
+        .map(|c| c.len_utf8())
+        .sum::<usize>();
+    line_start + column_bytes
+}
+
+/// describe json parse error with location (line, column, byte) and an excerpt of content,
+/// long lines will be cut around the error column.
+pub fn error_snippet(content: &str, err: &serde_json::Error) -> String {
+    let line = err.line();
+    let column = err.column();
+    let offset = error_offset(content, line, column);
+
+    let mut result = format!("line {line}, column {column} (byte {offset})\n\n");
+    let lines = content.lines().collect::<Vec<&str>>();
+    if line == 0 || lines.is_empty() {
+        return result;
+    }
+    let line = line.min(lines.len());
+    let first = line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    // the window keeps the error column visible for long lines
+    let skip = column.saturating_sub(SNIPPET_WIDTH / 2);
+    let number_width = line.to_string().len();
+    for number in first..=line {
+        let text = lines[number - 1]
+            .chars()
+            .skip(skip)
+            .take(SNIPPET_WIDTH)
+            .collect::<String>();
+        let prefix = if skip > 0 { "..." } else { "" };
+        result.push_str(&format!("{number:>number_width$} | {prefix}{text}\n"));
+    }
+    let marker_offset = number_width + 3 + if skip > 0 { 3 } else { 0 };
+    let caret = column.saturating_sub(skip).saturating_sub(1);
+    result.push_str(&format!("{}^", " ".repeat(marker_offset + caret)));
+    result
+}
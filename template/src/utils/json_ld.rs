@@ -0,0 +1,66 @@
+use serde_json::{json, Map, Value};
+
+use crate::{blog::PostInfo, utils::date::parse_date};
+
+/// id of the structured data script tag in document head.
+const SCRIPT_ID: &str = "karaty-json-ld";
+
+/// schema.org `BlogPosting` of the post, absent fields are omitted.
+pub fn blog_posting(info: &PostInfo) -> Value {
+    let mut result = Map::new();
+    result.insert("@context".to_string(), json!("https://schema.org"));
+    result.insert("@type".to_string(), json!("BlogPosting"));
+    result.insert("headline".to_string(), json!(info.title));
+    if let Some(date) = parse_date(&info.date) {
+        // date only value keeps `YYYY-MM-DD` format
+        let date = if info.date.trim().len() <= 10 {
+            date.format("%Y-%m-%d").to_string()
+        } else {
+            date.format("%Y-%m-%dT%H:%M:%S").to_string()
+        };
+        result.insert("datePublished".to_string(), json!(date));
+    }
+    if let Some(author) = &info.author {
+        result.insert("author".to_string(), json!({ "@type": "Person", "name": author }));
+    }
+    if !info.excerpt.is_empty() {
+        result.insert("description".to_string(), json!(info.excerpt));
+    }
+    if !info.tags.is_empty() {
+        result.insert("keywords".to_string(), json!(info.tags.join(", ")));
+    }
+    Value::Object(result)
+}
+
+/// structured data script in document head, it's removed when dropped (page unmounted).
+pub struct JsonLdScript;
+
+impl JsonLdScript {
+    pub fn set(data: &Value) -> Option<Self> {
+        let document = web_sys::window()?.document()?;
+        let script = match document.get_element_by_id(SCRIPT_ID) {
+            Some(script) => script,
+            None => {
+                let script = document.create_element("script").ok()?;
+                script.set_id(SCRIPT_ID);
+                script.set_attribute("type", "application/ld+json").ok()?;
+                document.head()?.append_child(&script).ok()?;
+                script
+            }
+        };
+        // text content is not parsed as html, `</script>` in values is safe
+        script.set_text_content(Some(&data.to_string()));
+        Some(Self)
+    }
+}
+
+impl Drop for JsonLdScript {
+    fn drop(&mut self) {
+        let script = web_sys::window()
+            .and_then(|v| v.document())
+            .and_then(|v| v.get_element_by_id(SCRIPT_ID));
+        if let Some(script) = script {
+            script.remove();
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use markdown::{mdast::Node, ParseOptions};
+
+/// content before this marker is used as excerpt of the post.
+pub const MORE_MARKER: &str = "<!--more-->";
+
+/// get the plain text excerpt of content: everything before `<!--more-->` marker,
+/// or the first paragraph cut to `max` characters when no marker exists.
+pub fn excerpt(content: &str, max: usize) -> String {
+    if let Some((summary, _)) = content.split_once(MORE_MARKER) {
+        return plain_text(summary, None);
+    }
+    let text = plain_text(content, Some(1));
+    if text.chars().count() > max {
+        let cut = text.chars().take(max).collect::<String>();
+        format!("{}...", cut.trim_end())
+    } else {
+        text
+    }
+}
+
+/// plain text of paragraphs in content, `limit` is the max number of paragraphs.
+fn plain_text(content: &str, limit: Option<usize>) -> String {
+    let mut list = vec![];
+    if let Ok(Node::Root(root)) = markdown::to_mdast(content, &ParseOptions::gfm()) {
+        for node in root.children {
+            if limit.map(|v| list.len() >= v).unwrap_or(false) {
+                break;
+            }
+            if let Node::Paragraph(_) = node {
+                list.push(node.to_string());
+            }
+        }
+    }
+    list.join(" ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_before_more_marker() {
+        let content = "First *paragraph*.\n\nSecond one.\n\n<!--more-->\n\nHidden part.";
+        assert_eq!(excerpt(content, 5), "First paragraph. Second one.");
+    }
+
+    #[test]
+    fn excerpt_without_more_marker() {
+        let content = "# Title\n\nFirst   paragraph\ntext.\n\nSecond one.";
+        assert_eq!(excerpt(content, 100), "First paragraph text.");
+        assert_eq!(excerpt(content, 6), "First...");
+    }
+}
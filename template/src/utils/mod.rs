@@ -0,0 +1,4 @@
+pub mod date;
+pub mod json;
+pub mod json_ld;
+pub mod markdown;